@@ -0,0 +1,70 @@
+//! Registers a custom joker and scores a round that uses it.
+//!
+//! ```text
+//! cargo run --example homebrew_joker
+//! ```
+
+use ortalab::balance::Balance;
+use ortalab::errors::GameResult;
+use ortalab::explain_dbg;
+use ortalab::game::GameState;
+use ortalab::input::parse_input;
+use ortalab::jokers::registry::register_joker;
+use ortalab::jokers::{ActivationType, JokerEffect, JokerInfo, Rarity};
+use ortalab::round::RoundInput;
+use ortalib::{Card, JokerCard, Rank};
+
+/// +7 Mult for every scored 7
+struct LuckySeven;
+
+impl JokerEffect for LuckySeven {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::OnScored
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        current_card: &Card,
+    ) -> GameResult<()> {
+        if current_card.rank == Rank::Seven {
+            // A balance file can tune the number like any other joker's
+            let mult_increase = game_state.balance.joker_value("Lucky Seven", 7.0);
+            game_state.mult += mult_increase;
+            let message = format!(
+                "{} {} +{} Mult ({} x {})",
+                game_state.joker_name(joker_card),
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
+            );
+            explain_dbg!(game_state, "{}", message);
+        }
+        Ok(())
+    }
+}
+
+const ROUND: &str = "\
+cards_played: [7♥, 7♠, 7♦, K♣, K♠]
+cards_held_in_hand: []
+jokers: [Blueprint, Lucky Seven Foil]
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    register_joker(
+        "Lucky Seven",
+        JokerInfo {
+            rarity: Rarity::Common,
+            cost: 5,
+            description: "+7 Mult for every scored 7",
+        },
+        Box::new(LuckySeven),
+    )?;
+
+    let input: RoundInput = parse_input("lucky_seven.yml".as_ref(), ROUND)?;
+    let (chips, mult) = ortalab::score_round(input, Balance::default(), None, true)?;
+    println!("{}", (chips * mult).floor());
+    Ok(())
+}
@@ -14,7 +14,7 @@ failed_tests=0
 # Function to run a single test
 run_test() {
     local test_file="$1"
-    local output_file="${test_file%.yml}.output"
+    local output_file="${test_file%.*}.output"
 
     # Skip if output file doesn't exist
     if [ ! -f "$output_file" ]; then
@@ -28,8 +28,15 @@ run_test() {
     # # Run the program and capture output
     # actual_output=$(cargo run --quiet -- "$test_file" --explain)
 
+    # Score with the balance file next to the round file, if there is one
+    local balance_file="${test_file%.*}.balance.toml"
+    local balance_args=()
+    if [ -f "$balance_file" ]; then
+        balance_args=(--balance "$balance_file")
+    fi
+
     # Run the program and capture output, redirecting stderr to /dev/null to ignore warnings
-    actual_output=$(cargo run --quiet -- "$test_file" --explain 2>/dev/null)
+    actual_output=$(cargo run --quiet -- "$test_file" --explain "${balance_args[@]}" 2>/dev/null)
 
     expected_output=$(cat "$output_file")
 
@@ -47,17 +54,74 @@ run_test() {
     fi
 }
 
-# Recursive function to find and test all yml files
+# Function to check that a Balatro save file imports to the round file next to it
+run_import_test() {
+    local save_file="$1"
+    local round_file="${save_file%.*}.yml"
+
+    # Skip if round file doesn't exist
+    if [ ! -f "$round_file" ]; then
+        echo -e "${YELLOW}Skipping $save_file - No round file found${NC}"
+        return
+    fi
+
+    echo -e "Importing: ${YELLOW}$save_file${NC}"
+    total_tests=$((total_tests + 1))
+
+    actual_output=$(cargo run --quiet -- import "$save_file" 2>/dev/null)
+
+    expected_output=$(cat "$round_file")
+
+    # Compare output (ignoring whitespace differences)
+    if [ "$(echo "$actual_output" | tr -d '[:space:]')" = "$(echo "$expected_output" | tr -d '[:space:]')" ]; then
+        echo -e "${GREEN}✓ PASSED${NC}"
+        passed_tests=$((passed_tests + 1))
+    else
+        echo -e "${RED}✗ FAILED${NC}"
+        echo -e "${YELLOW}Expected:${NC}"
+        echo "$expected_output"
+        echo -e "${YELLOW}Actual:${NC}"
+        echo "$actual_output"
+        failed_tests=$((failed_tests + 1))
+    fi
+}
+
+# Function to check that the committed round file schema matches `ortalab schema`
+run_schema_test() {
+    local schema_file="schema/round.schema.json"
+
+    echo -e "Checking schema: ${YELLOW}$schema_file${NC}"
+    total_tests=$((total_tests + 1))
+
+    if diff -u "$schema_file" <(cargo run --quiet -- schema 2>/dev/null); then
+        echo -e "${GREEN}✓ PASSED${NC}"
+        passed_tests=$((passed_tests + 1))
+    else
+        echo -e "${RED}✗ FAILED${NC}"
+        echo -e "${YELLOW}Regenerate it with: cargo run -- schema > $schema_file${NC}"
+        failed_tests=$((failed_tests + 1))
+    fi
+}
+
+# Recursive function to find and test all round files (YAML, JSON and RON)
+# and save files
 test_directory() {
     local dir="$1"
 
-    # Process all yml files in this directory
-    for test_file in "$dir"/*.yml; do
+    # Process all round files in this directory
+    for test_file in "$dir"/*.yml "$dir"/*.json "$dir"/*.ron; do
         if [ -f "$test_file" ]; then
             run_test "$test_file"
         fi
     done
 
+    # Process all save files in this directory
+    for save_file in "$dir"/*.jkr; do
+        if [ -f "$save_file" ]; then
+            run_import_test "$save_file"
+        fi
+    done
+
     # Recursively process subdirectories
     for subdir in "$dir"/*/; do
         if [ -d "$subdir" ]; then
@@ -78,6 +142,7 @@ main() {
     echo "Test directory: $test_dir"
 
     # Run all tests
+    run_schema_test
     test_directory "$test_dir"
 
     # Summary
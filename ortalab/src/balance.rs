@@ -0,0 +1,145 @@
+//! # Balance Module
+//!
+//! This module holds the scoring constants that a balance file can override, so
+//! that tuning can be tried against the fixtures without recompiling.
+//!
+//! `ortalab round.yml --balance balance.toml` scores the round with the numbers
+//! of the balance file; anything the file leaves out keeps Balatro's value:
+//!
+//! ```toml
+//! [hands]
+//! Pair = { chips = 15, mult = 2 }
+//!
+//! [enhancements]
+//! bonus = 40
+//! glass = 1.75
+//!
+//! [editions]
+//! polychrome = 2
+//!
+//! [jokers]
+//! "Greedy Joker" = 4
+//! Baron = 1.25
+//! ```
+//!
+//! Hand values are the values at level 1, to which levels still add
+//! `level_up_value()`. Edition numbers apply to cards and jokers alike. A
+//! joker's number is the one on its card: the 3 of Greedy Joker's "+3 Mult",
+//! the 1.5 of Baron's "x1.5 Mult" or the 0.2 of Obelisk's "x0.2 Mult per hand".
+//! Jokers without a number (Blueprint, Four Fingers) are not affected.
+//!
+//! Like other input files, a balance file can also be written as YAML, JSON or RON.
+//!
+//! ## Key Components
+//! - `Balance`: The contents of a balance file
+//! - `EnhancementValues`, `EditionValues`: The numbers of enhancements and editions
+//! - `Balance::joker_value()`: A joker's number, as tuned or its default
+
+use std::collections::HashMap;
+
+use ortalib::{Chips, Joker, Mult, PokerHand};
+use serde::Deserialize;
+
+use crate::game::STONE_CHIPS;
+use crate::history::HandName;
+use crate::jokers::registry::custom_joker;
+
+/// Base Chips and Mult of a poker hand at level 1; either can be left out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HandValue {
+    pub chips: Option<Chips>,
+    pub mult: Option<Mult>,
+}
+
+/// The numbers of card enhancements
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnhancementValues {
+    /// +Chips of a scored Bonus card
+    pub bonus: Chips,
+    /// +Mult of a scored Mult card
+    pub mult: Mult,
+    /// x Mult of a scored Glass card
+    pub glass: Mult,
+    /// x Mult of a Steel card held in hand
+    pub steel: Mult,
+    /// +Chips of a scored Stone card
+    pub stone: Chips,
+}
+
+impl Default for EnhancementValues {
+    fn default() -> Self {
+        Self {
+            bonus: 30.0,
+            mult: 4.0,
+            glass: 2.0,
+            steel: 1.5,
+            stone: STONE_CHIPS,
+        }
+    }
+}
+
+/// The numbers of card and joker editions
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EditionValues {
+    /// +Chips of a Foil card or joker
+    pub foil: Chips,
+    /// +Mult of a Holographic card or joker
+    pub holographic: Mult,
+    /// x Mult of a Polychrome card or joker
+    pub polychrome: Mult,
+}
+
+impl Default for EditionValues {
+    fn default() -> Self {
+        Self {
+            foil: 50.0,
+            holographic: 10.0,
+            polychrome: 1.5,
+        }
+    }
+}
+
+/// Scoring constants overriding Balatro's
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Balance {
+    pub hands: HashMap<HandName, HandValue>,
+    pub enhancements: EnhancementValues,
+    pub editions: EditionValues,
+    /// Each tuned joker's number, by the joker's name
+    pub jokers: HashMap<String, f64>,
+}
+
+impl Balance {
+    /// The base Chips and Mult of `hand` at level 1
+    pub fn hand_value(&self, hand: PokerHand) -> (Chips, Mult) {
+        let (chips, mult) = hand.hand_value();
+        match self.hands.get(&HandName(hand)) {
+            Some(value) => (value.chips.unwrap_or(chips), value.mult.unwrap_or(mult)),
+            None => (chips, mult),
+        }
+    }
+
+    /// The number of the joker called `joker`, or `default` if it is not tuned
+    pub fn joker_value(&self, joker: &str, default: f64) -> f64 {
+        if self.jokers.is_empty() {
+            return default;
+        }
+        self.jokers.get(joker).copied().unwrap_or(default)
+    }
+
+    /// Checks that every tuned joker is a built-in or registered joker
+    pub fn check(&self) -> Result<(), String> {
+        let unknown = self
+            .jokers
+            .keys()
+            .find(|name| name.parse::<Joker>().is_err() && custom_joker(name).is_none());
+        match unknown {
+            Some(name) => Err(format!("unknown joker `{}` in the balance file", name)),
+            None => Ok(()),
+        }
+    }
+}
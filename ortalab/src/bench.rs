@@ -0,0 +1,167 @@
+//! # Bench Module
+//!
+//! This module checks and times the two hand evaluators against each other.
+//!
+//! `poker::identify_hand()` is an allocation-free lookup-table evaluator used
+//! wherever hands are identified in bulk, while `poker::evaluate_hand()` builds
+//! the full hand breakdown used for scoring. Both must agree on the poker hand
+//! for every 5-card hand of a deck, under every combination of rule modifiers.
+//!
+//! ## Key Components
+//! - `compare_evaluators()`: Runs both evaluators over every 5-card hand of a deck
+//! - `EvaluatorComparison`: Mismatches and timings for one set of rule modifiers
+//!
+//! Build with `--release` for meaningful timings.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ortalib::{Card, PokerHand};
+
+use crate::errors::GameResult;
+use crate::poker::{evaluate_hand, identify_hand};
+
+/// Number of cards in each hand that is compared
+const HAND_SIZE: usize = 5;
+
+/// The results of comparing both evaluators with one set of rule modifiers
+#[derive(Debug, Clone)]
+pub struct EvaluatorComparison {
+    /// Active rule modifiers as (Four Fingers, Shortcut, Smeared Joker)
+    pub rules: (bool, bool, bool),
+    /// Number of hands evaluated
+    pub hands: usize,
+    /// Number of hands where the evaluators disagree
+    pub mismatches: usize,
+    /// The first hand where the evaluators disagree, if any
+    pub first_mismatch: Option<([Card; HAND_SIZE], PokerHand, PokerHand)>,
+    /// Time taken by `evaluate_hand`
+    pub breakdown_time: Duration,
+    /// Time taken by `identify_hand`
+    pub lookup_time: Duration,
+}
+
+impl fmt::Display for EvaluatorComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (four_fingers, shortcut, smeared) = self.rules;
+        let rules: Vec<&str> = [
+            (four_fingers, "Four Fingers"),
+            (shortcut, "Shortcut"),
+            (smeared, "Smeared Joker"),
+        ]
+        .into_iter()
+        .filter(|&(active, _)| active)
+        .map(|(_, name)| name)
+        .collect();
+        let rules = if rules.is_empty() {
+            "none".to_string()
+        } else {
+            rules.join(", ")
+        };
+
+        write!(
+            f,
+            "{:<37} {:>8} hands  {:>4} mismatches  breakdown {:>8.3}s  lookup {:>7.3}s  ({:.1}x)",
+            rules,
+            self.hands,
+            self.mismatches,
+            self.breakdown_time.as_secs_f64(),
+            self.lookup_time.as_secs_f64(),
+            self.breakdown_time.as_secs_f64() / self.lookup_time.as_secs_f64().max(f64::EPSILON)
+        )?;
+        if let Some((hand, breakdown, lookup)) = &self.first_mismatch {
+            let cards: Vec<String> = hand.iter().map(|card| card.to_string()).collect();
+            write!(
+                f,
+                "\n  first mismatch: [{}] breakdown {} / lookup {}",
+                cards.join(", "),
+                breakdown,
+                lookup
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Calls `visit` with every 5-card hand of `cards`, reusing one array
+fn for_each_hand(
+    cards: &[Card],
+    mut visit: impl FnMut(&[Card; HAND_SIZE]) -> GameResult<()>,
+) -> GameResult<()> {
+    let mut hand = [cards[0]; HAND_SIZE];
+    for (a, &first) in cards.iter().enumerate() {
+        hand[0] = first;
+        for (b, &second) in cards.iter().enumerate().skip(a + 1) {
+            hand[1] = second;
+            for (c, &third) in cards.iter().enumerate().skip(b + 1) {
+                hand[2] = third;
+                for (d, &fourth) in cards.iter().enumerate().skip(c + 1) {
+                    hand[3] = fourth;
+                    for &fifth in &cards[d + 1..] {
+                        hand[4] = fifth;
+                        visit(&hand)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compares both evaluators over every 5-card hand of `cards`, once for each
+/// combination of rule modifiers
+pub fn compare_evaluators(cards: &[Card]) -> GameResult<Vec<EvaluatorComparison>> {
+    if cards.len() < HAND_SIZE {
+        return Ok(Vec::new());
+    }
+
+    let mut comparisons = Vec::new();
+    for bits in 0..8 {
+        let rules = (bits & 1 != 0, bits & 2 != 0, bits & 4 != 0);
+        let (four_fingers, shortcut, smeared) = rules;
+
+        let start = Instant::now();
+        for_each_hand(cards, |hand| {
+            black_box(identify_hand(
+                black_box(hand),
+                four_fingers,
+                shortcut,
+                smeared,
+            )?);
+            Ok(())
+        })?;
+        let lookup_time = start.elapsed();
+
+        let start = Instant::now();
+        for_each_hand(cards, |hand| {
+            black_box(evaluate_hand(black_box(hand), four_fingers, shortcut, smeared).hand);
+            Ok(())
+        })?;
+        let breakdown_time = start.elapsed();
+
+        let mut hands = 0;
+        let mut mismatches = 0;
+        let mut first_mismatch = None;
+        for_each_hand(cards, |hand| {
+            hands += 1;
+            let breakdown = evaluate_hand(hand, four_fingers, shortcut, smeared).hand;
+            let lookup = identify_hand(hand, four_fingers, shortcut, smeared)?;
+            if breakdown != lookup {
+                mismatches += 1;
+                first_mismatch.get_or_insert((*hand, breakdown, lookup));
+            }
+            Ok(())
+        })?;
+
+        comparisons.push(EvaluatorComparison {
+            rules,
+            hands,
+            mismatches,
+            first_mismatch,
+            breakdown_time,
+            lookup_time,
+        });
+    }
+    Ok(comparisons)
+}
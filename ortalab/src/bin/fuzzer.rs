@@ -243,7 +243,7 @@ fn main() -> io::Result<()> {
     println!("Building solution...");
     let build_output = Command::new("cargo").arg("build").output()?;
     if !build_output.status.success() {
-        return Err(io::Error::new(io::ErrorKind::Other, "Build failed"));
+        return Err(io::Error::other("Build failed"));
     }
 
     // Run tests
@@ -0,0 +1,516 @@
+//! # Consumables Module
+//!
+//! This module handles Tarot and Spectral cards used before a hand is scored.
+//! Each consumable transforms the played cards or the cards held in hand,
+//! and all consumables are applied in order before `GameState::score` runs.
+//!
+//! ## Key Components
+//! - `Consumable`: The supported Tarot and Spectral cards
+//! - `ConsumableUse`: One use of a consumable, with the cards it targets
+//! - `apply_consumables()`: Validates targets and applies every use to a round and
+//!   its card extras, returning the cards that were destroyed
+//!
+//! ## Targets
+//! Targets are written as `played N` or `held N`, counting from 1.
+//! They refer to the cards as they are when that consumable is used,
+//! so destroying or creating cards shifts the targets of later consumables.
+//!
+//! ## Card Extras
+//! A changed card keeps its extras (seal, debuff, face down, ...), and the
+//! explain output shows it through its label, so face-down cards stay hidden.
+//! Death replaces the extras of its target with those of the card it copies, and
+//! the copies Cryptid makes get the extras of their source. A card made by
+//! `Card::new` never equals an existing card, so these extras are copied over.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use enum_iterator::{Sequence, all, first};
+use ortalib::{Card, Edition, Enhancement, Rank, Round, Suit};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::Deserialize;
+
+use crate::errors::{GameError, GameResult};
+use crate::explain_dbg_bool;
+use crate::extras::{CardExtras, CardLabel};
+
+/// The Tarot and Spectral cards that can be used before scoring
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Sequence)]
+#[serde(try_from = "String")]
+pub enum Consumable {
+    // Tarot cards
+    TheEmpress,
+    TheHierophant,
+    TheLovers,
+    TheChariot,
+    Justice,
+    Strength,
+    TheHangedMan,
+    Death,
+    TheStar,
+    TheMoon,
+    TheSun,
+    TheWorld,
+
+    // Spectral cards
+    Aura,
+    Cryptid,
+}
+
+impl Consumable {
+    /// The minimum and maximum number of cards this consumable can target
+    fn target_range(&self) -> (usize, usize) {
+        match self {
+            Consumable::TheEmpress
+            | Consumable::TheHierophant
+            | Consumable::Strength
+            | Consumable::TheHangedMan => (1, 2),
+            Consumable::TheLovers
+            | Consumable::TheChariot
+            | Consumable::Justice
+            | Consumable::Aura
+            | Consumable::Cryptid => (1, 1),
+            Consumable::Death => (2, 2),
+            Consumable::TheStar
+            | Consumable::TheMoon
+            | Consumable::TheSun
+            | Consumable::TheWorld => (1, 3),
+        }
+    }
+}
+
+impl fmt::Display for Consumable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[rustfmt::skip]
+        let name = match self {
+            Consumable::TheEmpress =>    "The Empress",
+            Consumable::TheHierophant => "The Hierophant",
+            Consumable::TheLovers =>     "The Lovers",
+            Consumable::TheChariot =>    "The Chariot",
+            Consumable::Justice =>       "Justice",
+            Consumable::Strength =>      "Strength",
+            Consumable::TheHangedMan =>  "The Hanged Man",
+            Consumable::Death =>         "Death",
+            Consumable::TheStar =>       "The Star",
+            Consumable::TheMoon =>       "The Moon",
+            Consumable::TheSun =>        "The Sun",
+            Consumable::TheWorld =>      "The World",
+            Consumable::Aura =>          "Aura",
+            Consumable::Cryptid =>       "Cryptid",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Consumable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[rustfmt::skip]
+        let value = match s {
+            "The Empress" =>    Consumable::TheEmpress,
+            "The Hierophant" => Consumable::TheHierophant,
+            "The Lovers" =>     Consumable::TheLovers,
+            "The Chariot" =>    Consumable::TheChariot,
+            "Justice" =>        Consumable::Justice,
+            "Strength" =>       Consumable::Strength,
+            "The Hanged Man" => Consumable::TheHangedMan,
+            "Death" =>          Consumable::Death,
+            "The Star" =>       Consumable::TheStar,
+            "The Moon" =>       Consumable::TheMoon,
+            "The Sun" =>        Consumable::TheSun,
+            "The World" =>      Consumable::TheWorld,
+            "Aura" =>           Consumable::Aura,
+            "Cryptid" =>        Consumable::Cryptid,
+            _ => return Err(format!("Invalid Consumable: `{}`", s)),
+        };
+        Ok(value)
+    }
+}
+
+impl TryFrom<String> for Consumable {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl JsonSchema for Consumable {
+    fn schema_name() -> Cow<'static, str> {
+        "Consumable".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        let names: Vec<String> = all::<Consumable>().map(|c| c.to_string()).collect();
+        json_schema!({
+            "type": "string",
+            "enum": names
+        })
+    }
+}
+
+/// Where a targeted card currently is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    Played,
+    Held,
+}
+
+/// A reference to one card of the round, e.g. `played 1` or `held 3`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CardRef {
+    pub zone: Zone,
+    /// Zero-based position within the zone
+    pub index: usize,
+}
+
+impl FromStr for CardRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (zone, position) = s
+            .split_once(' ')
+            .ok_or_else(|| format!("Invalid target `{}`, expected `played N` or `held N`", s))?;
+        let zone = match zone {
+            "played" => Zone::Played,
+            "held" => Zone::Held,
+            _ => return Err(format!("Invalid target zone `{}` in `{}`", zone, s)),
+        };
+        let position: usize = position
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid target position in `{}`", s))?;
+        if position == 0 {
+            return Err(format!("Target `{}` must count from 1", s));
+        }
+
+        Ok(CardRef {
+            zone,
+            index: position - 1,
+        })
+    }
+}
+
+impl TryFrom<String> for CardRef {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl JsonSchema for CardRef {
+    fn schema_name() -> Cow<'static, str> {
+        "CardRef".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A played or held card, counting from 1",
+            "pattern": "^(played|held) [1-9][0-9]*$",
+            "examples": ["played 1", "held 3"]
+        })
+    }
+}
+
+impl fmt::Display for CardRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let zone = match self.zone {
+            Zone::Played => "played",
+            Zone::Held => "held",
+        };
+        write!(f, "{} {}", zone, self.index + 1)
+    }
+}
+
+/// One use of a consumable in the round file
+///
+/// ```yaml
+/// consumables:
+///   - name: The Empress
+///     targets: [played 1, played 2]
+///   - name: Aura
+///     targets: [held 1]
+///     edition: Polychrome
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ConsumableUse {
+    pub name: Consumable,
+    #[serde(default)]
+    pub targets: Vec<CardRef>,
+    /// The edition added by Aura (chosen at random in game)
+    #[serde(default, deserialize_with = "deserialize_edition")]
+    #[schemars(schema_with = "edition_schema")]
+    pub edition: Option<Edition>,
+}
+
+fn edition_schema(_generator: &mut SchemaGenerator) -> Schema {
+    let names: Vec<String> = all::<Edition>().map(|e| e.to_string()).collect();
+    json_schema!({
+        "type": "string",
+        "enum": names
+    })
+}
+
+fn deserialize_edition<'de, D>(deserializer: D) -> Result<Option<Edition>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let name: Option<String> = Option::deserialize(deserializer)?;
+    name.map(|name| name.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// Returns the cards of a zone in the round
+fn zone_cards(round: &mut Round, zone: Zone) -> &mut Vec<Card> {
+    match zone {
+        Zone::Played => &mut round.cards_played,
+        Zone::Held => &mut round.cards_held_in_hand,
+    }
+}
+
+/// Returns the targeted card, failing if the position is out of range
+fn target_card(round: &mut Round, target: CardRef) -> GameResult<&mut Card> {
+    zone_cards(round, target.zone)
+        .get_mut(target.index)
+        .ok_or_else(|| GameError::InvalidConsumable(format!("there is no card at `{}`", target)))
+}
+
+/// Checks the number of targets and that each target exists and is unique
+fn validate_targets(round: &mut Round, consumable_use: &ConsumableUse) -> GameResult<()> {
+    let consumable = consumable_use.name;
+    let targets = &consumable_use.targets;
+    let (min, max) = consumable.target_range();
+
+    if targets.len() < min || targets.len() > max {
+        let expected = if min == max {
+            min.to_string()
+        } else {
+            format!("{} to {}", min, max)
+        };
+        return Err(GameError::InvalidConsumable(format!(
+            "{} needs {} targets, but {} were given",
+            consumable,
+            expected,
+            targets.len()
+        )));
+    }
+
+    for (i, target) in targets.iter().enumerate() {
+        if targets[..i].contains(target) {
+            return Err(GameError::InvalidConsumable(format!(
+                "{} targets `{}` more than once",
+                consumable, target
+            )));
+        }
+        target_card(round, *target)?;
+    }
+
+    match (consumable, consumable_use.edition) {
+        (Consumable::Aura, None) => Err(GameError::InvalidConsumable(format!(
+            "{} needs an `edition` to add",
+            consumable
+        ))),
+        (Consumable::Aura, Some(_)) => {
+            let card = target_card(round, targets[0])?;
+            if card.edition.is_some() {
+                return Err(GameError::InvalidConsumable(format!(
+                    "{} cannot target {} as it already has an edition",
+                    consumable, card
+                )));
+            }
+            Ok(())
+        }
+        (_, Some(_)) => Err(GameError::InvalidConsumable(format!(
+            "{} does not take an `edition`",
+            consumable
+        ))),
+        (_, None) => Ok(()),
+    }
+}
+
+/// Returns the next rank up, with Aces wrapping around to Twos
+fn rank_up(rank: Rank) -> Rank {
+    rank.next()
+        .unwrap_or_else(|| first::<Rank>().unwrap_or(rank))
+}
+
+/// Whether any played or held card of the round has the value of `card`
+fn in_round(round: &Round, card: &Card) -> bool {
+    round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .any(|other| other == card)
+}
+
+/// Gives a changed card the extras it had before the change
+///
+/// Extras are keyed by the card's value, so a card that changes would otherwise
+/// lose them. Other cards of the round that still have the old value keep them too.
+fn move_extras(
+    round: &Round,
+    card_extras: &mut HashMap<Card, CardExtras>,
+    before: Card,
+    after: Card,
+) {
+    if before == after {
+        return;
+    }
+    let Some(extras) = card_extras.get(&before).copied() else {
+        return;
+    };
+    if !in_round(round, &before) {
+        card_extras.remove(&before);
+    }
+    card_extras.insert(after, extras);
+}
+
+/// Applies one consumable use to the round, collecting any destroyed cards
+fn apply_consumable(
+    round: &mut Round,
+    card_extras: &mut HashMap<Card, CardExtras>,
+    consumable_use: &ConsumableUse,
+    destroyed_cards: &mut Vec<(Card, Consumable)>,
+    explain_enabled: bool,
+) -> GameResult<()> {
+    validate_targets(round, consumable_use)?;
+    let consumable = consumable_use.name;
+    let targets = &consumable_use.targets;
+
+    // Changes every targeted card in place, keeping its extras
+    let change_targets = |round: &mut Round,
+                          card_extras: &mut HashMap<Card, CardExtras>,
+                          change: &dyn Fn(&mut Card)|
+     -> GameResult<()> {
+        for target in targets {
+            let card = target_card(round, *target)?;
+            let before = *card;
+            change(card);
+            let after = *card;
+            let extras = card_extras.get(&before).copied().unwrap_or_default();
+            move_extras(round, card_extras, before, after);
+            explain_dbg_bool!(
+                explain_enabled,
+                "{} {} -> {}",
+                consumable,
+                CardLabel::new(&before, extras),
+                CardLabel::new(&after, extras)
+            );
+        }
+        Ok(())
+    };
+    let set_enhancement =
+        |enhancement: Enhancement| move |card: &mut Card| card.enhancement = Some(enhancement);
+    let set_suit = |suit: Suit| move |card: &mut Card| card.suit = suit;
+
+    match consumable {
+        Consumable::TheEmpress => {
+            change_targets(round, card_extras, &set_enhancement(Enhancement::Mult))?
+        }
+        Consumable::TheHierophant => {
+            change_targets(round, card_extras, &set_enhancement(Enhancement::Bonus))?
+        }
+        Consumable::TheLovers => {
+            change_targets(round, card_extras, &set_enhancement(Enhancement::Wild))?
+        }
+        Consumable::TheChariot => {
+            change_targets(round, card_extras, &set_enhancement(Enhancement::Steel))?
+        }
+        Consumable::Justice => {
+            change_targets(round, card_extras, &set_enhancement(Enhancement::Glass))?
+        }
+        Consumable::TheStar => change_targets(round, card_extras, &set_suit(Suit::Diamonds))?,
+        Consumable::TheMoon => change_targets(round, card_extras, &set_suit(Suit::Clubs))?,
+        Consumable::TheSun => change_targets(round, card_extras, &set_suit(Suit::Hearts))?,
+        Consumable::TheWorld => change_targets(round, card_extras, &set_suit(Suit::Spades))?,
+        Consumable::Strength => {
+            change_targets(round, card_extras, &|card| card.rank = rank_up(card.rank))?
+        }
+        Consumable::Aura => {
+            let edition = consumable_use.edition;
+            change_targets(round, card_extras, &|card| card.edition = edition)?
+        }
+        Consumable::Death => {
+            // The first target becomes a copy of the second, extras included
+            let source = *target_card(round, targets[1])?;
+            let source_extras = card_extras.get(&source).copied();
+            let card = target_card(round, targets[0])?;
+            let before = *card;
+            *card = Card::new(source.rank, source.suit, source.enhancement, source.edition);
+            let after = *card;
+            let before_extras = card_extras.get(&before).copied().unwrap_or_default();
+            // The target's own extras are replaced by the source's; a new card
+            // never shares its value with the source, so they are copied over
+            if !in_round(round, &before) {
+                card_extras.remove(&before);
+            }
+            if let Some(extras) = source_extras {
+                card_extras.insert(after, extras);
+            }
+            explain_dbg_bool!(
+                explain_enabled,
+                "{} {} -> {}",
+                consumable,
+                CardLabel::new(&before, before_extras),
+                CardLabel::new(&after, source_extras.unwrap_or_default())
+            );
+        }
+        Consumable::Cryptid => {
+            // Copies are added to the cards held in hand, each with the source's extras
+            let source = *target_card(round, targets[0])?;
+            let source_extras = card_extras.get(&source).copied();
+            for _ in 0..2 {
+                let copy = Card::new(source.rank, source.suit, source.enhancement, source.edition);
+                if let Some(extras) = source_extras {
+                    card_extras.insert(copy, extras);
+                }
+                round.cards_held_in_hand.push(copy);
+            }
+            explain_dbg_bool!(
+                explain_enabled,
+                "{} {} copied twice into hand",
+                consumable,
+                CardLabel::new(&source, source_extras.unwrap_or_default())
+            );
+        }
+        Consumable::TheHangedMan => {
+            // Remove from the back so earlier positions stay valid
+            let mut sorted = targets.clone();
+            sorted.sort_by_key(|target| std::cmp::Reverse(target.index));
+            for target in sorted {
+                let destroyed = zone_cards(round, target.zone).remove(target.index);
+                destroyed_cards.push((destroyed, consumable));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies every consumable use to the round, in order
+///
+/// Returns the destroyed cards together with the consumable that destroyed them.
+pub fn apply_consumables(
+    round: &mut Round,
+    card_extras: &mut HashMap<Card, CardExtras>,
+    uses: &[ConsumableUse],
+    explain_enabled: bool,
+) -> GameResult<Vec<(Card, Consumable)>> {
+    let mut destroyed_cards = Vec::new();
+    for consumable_use in uses {
+        apply_consumable(
+            round,
+            card_extras,
+            consumable_use,
+            &mut destroyed_cards,
+            explain_enabled,
+        )?;
+    }
+    Ok(destroyed_cards)
+}
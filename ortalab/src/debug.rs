@@ -1,13 +1,52 @@
 //! # Debug Module
 //!
 //! This module provides macros for conditional debug output in the game.
+//!
+//! Explain lines are printed as they happen. While a replay is being recorded
+//! they are also kept, and printing can be turned off so a run can be recorded
+//! without showing its explanation.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+thread_local! {
+    /// Explain lines recorded so far, while recording
+    static RECORDING: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    /// Whether explain lines are printed
+    static PRINTING: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Prints and/or records one explain line
+pub fn explain(args: fmt::Arguments<'_>) {
+    let recorded = RECORDING.with_borrow_mut(|recording| {
+        recording
+            .as_mut()
+            .map(|lines| lines.push(args.to_string()))
+            .is_some()
+    });
+    if !recorded || PRINTING.get() {
+        println!("{}", args);
+    }
+}
+
+/// Starts recording explain lines, printing them only if `print` is set
+pub fn start_recording(print: bool) {
+    RECORDING.set(Some(Vec::new()));
+    PRINTING.set(print);
+}
+
+/// Stops recording and returns the recorded explain lines
+pub fn finish_recording() -> Vec<String> {
+    PRINTING.set(true);
+    RECORDING.take().unwrap_or_default()
+}
 
 /// Prints debug information based on a boolean flag.
 #[macro_export]
 macro_rules! explain_dbg_bool {
     ($enabled:expr, $($arg:tt)*) => {
         if $enabled {
-            println!($($arg)*);
+            $crate::debug::explain(format_args!($($arg)*));
         }
     };
 }
@@ -17,7 +56,7 @@ macro_rules! explain_dbg_bool {
 macro_rules! explain_dbg {
     ($state:expr, $($arg:tt)*) => {
         if $state.explain_enabled {
-            println!($($arg)*);
+            $crate::debug::explain(format_args!($($arg)*));
         }
     };
 }
@@ -0,0 +1,436 @@
+//! # Deck Module
+//!
+//! This module models the deck that cards are drawn from during a round.
+//!
+//! ## Key Components
+//! - `Deck`: An ordered pile of cards supporting seeded shuffles and draws
+//! - `DeckSpec`: A YAML description of a custom deck (base deck and variant plus added/removed cards)
+//! - `HandOdds`: The distribution of the best poker hand reachable after a draw
+//! - `HandStats`: How rule-bending jokers shift the hand types of random hands
+//!
+//! ## Probability Queries
+//! `Deck::hand_odds()` answers questions such as "what is the chance of holding a
+//! Flush after the next draw, given the cards I am keeping". When the number of
+//! possible draws is small every draw is enumerated, otherwise a seeded sample is used.
+//!
+//! `Deck::hand_stats()` runs the same query for a fresh random hand twice, once
+//! without jokers and once with the given rule-bending jokers, so the two
+//! distributions can be compared hand type by hand type.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use enum_iterator::all;
+use indexmap::IndexMap;
+use itertools::Itertools;
+use ortalib::{Card, Joker, JokerCard, PokerHand};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use serde::Deserialize;
+
+use crate::errors::{GameError, GameResult};
+use crate::extras::{CardExtras, CardSpec};
+use crate::poker::{active_rules, identify_hand};
+use crate::variants::DeckVariant;
+
+/// Number of cards a player holds at the start of each hand
+pub const DEFAULT_HAND_SIZE: usize = 8;
+
+/// Maximum number of cards that can be played as a single poker hand
+const MAX_PLAYED_CARDS: usize = 5;
+
+/// Draws are enumerated exactly when there are at most this many of them
+const MAX_EXACT_DRAWS: usize = 20_000;
+
+/// The starting point for a custom deck
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeckBase {
+    /// The 52 cards of the deck variant
+    #[default]
+    Standard,
+    /// A deck with no cards, built entirely from `add`
+    Empty,
+}
+
+/// Describes a custom deck composition
+///
+/// For example, a standard deck with an extra Glass Ace, a Gold King
+/// and no Twos of Clubs:
+/// ```yaml
+/// base: standard
+/// variant: checkered
+/// add:
+///   - A♠ Glass
+///   - K♥ Gold
+/// remove:
+///   - 2♣
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeckSpec {
+    #[serde(default)]
+    pub base: DeckBase,
+    /// The deck variant whose cards a standard base starts with
+    #[serde(default)]
+    pub variant: DeckVariant,
+    /// Seed for the cards of an Erratic Deck
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub add: Vec<CardSpec>,
+    #[serde(default)]
+    pub remove: Vec<Card>,
+}
+
+/// Returns true if both cards have the same rank, suit, enhancement and edition
+///
+/// `Card` equality in ortalib is identity based, so two separately parsed
+/// `A♠` cards never compare equal. Deck bookkeeping needs value equality instead.
+pub fn same_card(a: &Card, b: &Card) -> bool {
+    a.rank == b.rank && a.suit == b.suit && a.enhancement == b.enhancement && a.edition == b.edition
+}
+
+/// An ordered pile of cards, drawn from the end
+#[derive(Debug, Clone, Default)]
+pub struct Deck {
+    cards: Vec<Card>,
+    /// Extras of every card that has been part of this deck, kept after drawing
+    extras: HashMap<Card, CardExtras>,
+}
+
+impl Deck {
+    /// Creates the regular 52 card deck, one card of every rank and suit
+    pub fn standard() -> Self {
+        Self::from_variant(DeckVariant::Standard, 0)
+    }
+
+    /// Creates the 52 card deck of a deck variant
+    pub fn from_variant(variant: DeckVariant, seed: u64) -> Self {
+        Self {
+            cards: variant.cards(seed),
+            extras: HashMap::new(),
+        }
+    }
+
+    /// Creates a deck holding `cards`, from bottom to top
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Self {
+            cards,
+            extras: HashMap::new(),
+        }
+    }
+
+    /// Gives the deck's cards the extras they have in `extras`
+    pub fn with_extras(mut self, extras: &HashMap<Card, CardExtras>) -> Self {
+        for card in &self.cards {
+            if let Some(card_extras) = extras.get(card) {
+                self.extras.insert(*card, *card_extras);
+            }
+        }
+        self
+    }
+
+    /// Creates a deck from a custom composition
+    pub fn from_spec(spec: &DeckSpec) -> GameResult<Self> {
+        let mut deck = match spec.base {
+            DeckBase::Standard => Self::from_variant(spec.variant, spec.seed),
+            DeckBase::Empty => Self::default(),
+        };
+
+        for card in &spec.remove {
+            if !deck.remove(card) {
+                return Err(GameError::InvalidDeck(format!(
+                    "cannot remove {} as it is not in the deck",
+                    card
+                )));
+            }
+        }
+        for spec in &spec.add {
+            deck.add(spec.card);
+            deck.extras.insert(spec.card, spec.extras);
+        }
+
+        Ok(deck)
+    }
+
+    /// The cards left in the deck, from bottom to top
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// The extras of a card from this deck, even if it has since been drawn
+    pub fn extras(&self, card: &Card) -> CardExtras {
+        self.extras.get(card).copied().unwrap_or_default()
+    }
+
+    /// The extras of every card that has been part of this deck
+    pub fn card_extras(&self) -> &HashMap<Card, CardExtras> {
+        &self.extras
+    }
+
+    /// Adds a card to the bottom of the deck
+    pub fn add(&mut self, card: Card) {
+        self.cards.insert(0, card);
+    }
+
+    /// Removes one card matching `card` by value, returning whether one was found
+    pub fn remove(&mut self, card: &Card) -> bool {
+        match self.cards.iter().position(|c| same_card(c, card)) {
+            Some(index) => {
+                self.cards.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every card that is already somewhere else (played, held, discarded)
+    ///
+    /// Cards that are not in the deck are ignored, so a hand containing
+    /// duplicates or added cards does not make the query fail.
+    pub fn remove_all(&mut self, cards: &[Card]) {
+        for card in cards {
+            self.remove(card);
+        }
+    }
+
+    /// Shuffles the deck deterministically from a seed
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.cards.shuffle(&mut rng);
+    }
+
+    /// Draws the top card of the deck
+    pub fn draw(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Draws cards into `hand` until it holds `hand_size` cards or the deck runs out
+    ///
+    /// Every Negative card in hand adds one to the hand size, including the ones
+    /// just drawn. Returns the number of cards drawn.
+    pub fn draw_to_hand_size(&mut self, hand: &mut Vec<Card>, hand_size: usize) -> usize {
+        let mut drawn = 0;
+        let mut hand_size = hand_size
+            + hand
+                .iter()
+                .filter(|card| self.extras(card).negative)
+                .count();
+        while hand.len() < hand_size {
+            match self.draw() {
+                Some(card) => {
+                    if self.extras(&card).negative {
+                        hand_size += 1;
+                    }
+                    hand.push(card);
+                    drawn += 1;
+                }
+                None => break,
+            }
+        }
+        drawn
+    }
+
+    /// Computes the distribution of the best poker hand available after
+    /// drawing from this deck until `held` reaches `hand_size` cards
+    ///
+    /// Rule-bending jokers (Four Fingers, Shortcut, Smeared Joker) among `jokers`
+    /// are taken into account. If there are more than `MAX_EXACT_DRAWS` possible
+    /// draws, `samples` random draws seeded with `seed` are used instead.
+    pub fn hand_odds(
+        &self,
+        held: &[Card],
+        hand_size: usize,
+        jokers: &[JokerCard],
+        samples: usize,
+        seed: u64,
+    ) -> GameResult<HandOdds> {
+        let draws = hand_size.saturating_sub(held.len()).min(self.len());
+        let rules = active_rules(jokers);
+
+        let mut counts: IndexMap<PokerHand, usize> = all::<PokerHand>().map(|h| (h, 0)).collect();
+        let mut hand = held.to_vec();
+        let mut record = |drawn: &[&Card]| -> GameResult<()> {
+            hand.truncate(held.len());
+            hand.extend(drawn.iter().map(|&&card| card));
+            *counts.entry(best_hand(&hand, rules)?).or_insert(0) += 1;
+            Ok(())
+        };
+
+        let exact = combinations_at_most(self.len(), draws, MAX_EXACT_DRAWS);
+        let total = if exact {
+            let mut total = 0;
+            for drawn in self.cards.iter().combinations(draws) {
+                record(&drawn)?;
+                total += 1;
+            }
+            total
+        } else {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for _ in 0..samples {
+                let drawn: Vec<&Card> = self.cards.choose_multiple(&mut rng, draws).collect();
+                record(&drawn)?;
+            }
+            samples
+        };
+
+        let probabilities = counts
+            .into_iter()
+            .map(|(hand, count)| (hand, count as f64 / total.max(1) as f64))
+            .collect();
+
+        Ok(HandOdds {
+            draws,
+            outcomes: total,
+            exact,
+            probabilities,
+        })
+    }
+
+    /// Computes the hand-type distribution of a random `hand_size`-card hand,
+    /// with and without the given jokers
+    ///
+    /// Both distributions are computed from the same draws (the same seed is used
+    /// when sampling), so differences come from the jokers rather than sampling noise.
+    pub fn hand_stats(
+        &self,
+        hand_size: usize,
+        jokers: &[JokerCard],
+        samples: usize,
+        seed: u64,
+    ) -> GameResult<HandStats> {
+        Ok(HandStats {
+            jokers: jokers.iter().map(|joker_card| joker_card.joker).collect(),
+            base: self.hand_odds(&[], hand_size, &[], samples, seed)?,
+            with_jokers: self.hand_odds(&[], hand_size, jokers, samples, seed)?,
+        })
+    }
+}
+
+/// Returns true if choosing `k` items from `n` gives at most `limit` combinations
+fn combinations_at_most(n: usize, k: usize, limit: usize) -> bool {
+    let k = k.min(n - k);
+    let mut combinations: usize = 1;
+    for i in 0..k {
+        combinations = combinations * (n - i) / (i + 1);
+        if combinations > limit {
+            return false;
+        }
+    }
+    true
+}
+
+/// Finds the best poker hand that can be played from the cards in a hand
+fn best_hand(
+    hand: &[Card],
+    (four_fingers, shortcut, smeared): (bool, bool, bool),
+) -> GameResult<PokerHand> {
+    if hand.len() <= MAX_PLAYED_CARDS {
+        return identify_hand(hand, four_fingers, shortcut, smeared);
+    }
+
+    let mut best = PokerHand::HighCard;
+    for played in hand.iter().copied().combinations(MAX_PLAYED_CARDS) {
+        best = best.max(identify_hand(&played, four_fingers, shortcut, smeared)?);
+    }
+    Ok(best)
+}
+
+/// The distribution of the best poker hand reachable after one draw
+#[derive(Debug, Clone)]
+pub struct HandOdds {
+    /// Number of cards drawn
+    pub draws: usize,
+    /// Number of draws enumerated or sampled
+    pub outcomes: usize,
+    /// Whether every possible draw was enumerated
+    pub exact: bool,
+    /// Probability that each poker hand is the best one available
+    pub probabilities: IndexMap<PokerHand, f64>,
+}
+
+impl HandOdds {
+    /// Probability that the best available hand is `hand` or better
+    pub fn at_least(&self, hand: PokerHand) -> f64 {
+        self.probabilities
+            .iter()
+            .filter(|&(&h, _)| h >= hand)
+            .map(|(_, &p)| p)
+            .sum()
+    }
+}
+
+impl fmt::Display for HandOdds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = if self.exact { "exact" } else { "sampled" };
+        writeln!(
+            f,
+            "Drawing {} cards ({} {} outcomes)",
+            self.draws, self.outcomes, method
+        )?;
+        for (hand, probability) in self.probabilities.iter().rev() {
+            writeln!(
+                f,
+                "{:<16} {:>7.3}%  (at least: {:>7.3}%)",
+                hand.to_string(),
+                probability * 100.0,
+                self.at_least(*hand) * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Hand-type frequencies of random hands, with and without rule-bending jokers
+#[derive(Debug, Clone)]
+pub struct HandStats {
+    /// The jokers that were active for `with_jokers`
+    pub jokers: Vec<Joker>,
+    /// The distribution without any jokers
+    pub base: HandOdds,
+    /// The distribution with the jokers active
+    pub with_jokers: HandOdds,
+}
+
+impl fmt::Display for HandStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = if self.base.exact { "exact" } else { "sampled" };
+        let jokers = if self.jokers.is_empty() {
+            "none".to_string()
+        } else {
+            self.jokers.iter().join(", ")
+        };
+        writeln!(
+            f,
+            "Random {}-card hands ({} {} outcomes)",
+            self.base.draws, self.base.outcomes, method
+        )?;
+        writeln!(f, "Jokers: {}", jokers)?;
+        writeln!(
+            f,
+            "{:<16} {:>8}  {:>8}  {:>8}",
+            "Hand", "Base", "Jokers", "Shift"
+        )?;
+        for (hand, base) in self.base.probabilities.iter().rev() {
+            let with_jokers = self.with_jokers.probabilities[hand];
+            writeln!(
+                f,
+                "{:<16} {:>7.3}%  {:>7.3}%  {:>+7.3}%",
+                hand.to_string(),
+                base * 100.0,
+                with_jokers * 100.0,
+                (with_jokers - base) * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,573 @@
+//! # Economy Module
+//!
+//! This module simulates money across a full run: scoring hands drawn from the deck,
+//! collecting blind rewards and interest, and buying or selling jokers in the shop.
+//!
+//! ## Key Components
+//! - `RunConfig`: The YAML description of a run (seed, stake, deck, starting jokers, shop, policy)
+//! - `ShopPolicy`: Trait deciding what to buy and sell each time the shop is visited
+//! - `simulate_run()`: Plays every blind of every ante and returns a per-ante log
+//!
+//! ## Run Structure
+//! Each ante has a Small, Big and Boss blind. For every blind a fresh hand is dealt
+//! from the shuffled deck and the best scoring play is made with `GameState::score`.
+//! Blinds are assumed to be cleared by that single hand, so every remaining hand
+//! pays out $1. The shop is visited after every blind. The log shows each score
+//! next to the blind's target, which depends on the stake and deck variant.
+//!
+//! ## Money Sources
+//! - Blind rewards ($3 Small, $4 Big, $5 Boss; no Small Blind reward from Red Stake up)
+//! - $1 per remaining hand
+//! - Interest of $1 per $5 held, up to $5
+//! - Gold Seal cards played and scored ($3 each)
+//! - Gold cards held in hand at the end of the round ($3 each)
+//! - Selling jokers for half their cost
+
+use std::fmt;
+
+use enum_iterator::all;
+use itertools::Itertools;
+use ortalib::{Card, Edition, Joker, JokerCard, Round};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::deck::{DEFAULT_HAND_SIZE, Deck, DeckSpec};
+use crate::errors::{GameError, GameResult};
+use crate::extras::{ExtraEnhancement, Seal};
+use crate::game::GameState;
+use crate::jokers::joker_info;
+use crate::jokers::registry::CustomJoker;
+use crate::round::DEFAULT_JOKER_SLOTS;
+use crate::variants::{Stake, blind_target};
+
+/// Maximum number of cards that can be played as a single poker hand
+pub(crate) const MAX_PLAYED_CARDS: usize = 5;
+
+/// Interest is $1 for every this many dollars held
+const INTEREST_STEP: u32 = 5;
+
+/// Interest earned at the end of a round is capped at this amount
+const MAX_INTEREST: u32 = 5;
+
+/// Money earned for each Gold Seal card that scores
+const GOLD_SEAL_MONEY: u32 = 3;
+
+/// Money earned for each Gold card held in hand at the end of the round
+const GOLD_CARD_MONEY: u32 = 3;
+
+/// The three blinds that make up an ante
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Blind {
+    Small,
+    Big,
+    Boss,
+}
+
+impl Blind {
+    pub const ALL: [Blind; 3] = [Blind::Small, Blind::Big, Blind::Boss];
+
+    /// Money earned for clearing this blind
+    pub fn reward(&self) -> u32 {
+        match self {
+            Blind::Small => 3,
+            Blind::Big => 4,
+            Blind::Boss => 5,
+        }
+    }
+
+    /// Multiplier of the ante's base target for this blind
+    pub fn scaling(&self) -> f64 {
+        match self {
+            Blind::Small => 1.0,
+            Blind::Big => 1.5,
+            Blind::Boss => 2.0,
+        }
+    }
+}
+
+impl fmt::Display for Blind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{:?} Blind", self))
+    }
+}
+
+/// Shop purchase price of a joker, including its edition
+///
+/// `custom` is the registered joker the card stands for, if any: its card is
+/// only a stand-in, so the registered joker's own cost is used.
+pub fn joker_cost(joker_card: &JokerCard, custom: Option<&CustomJoker>) -> u32 {
+    let base = match custom {
+        Some(custom) => custom.info.cost,
+        None => joker_info(joker_card.joker).cost,
+    };
+
+    let edition = match joker_card.edition {
+        Some(Edition::Foil) => 2,
+        Some(Edition::Holographic) => 3,
+        Some(Edition::Polychrome) => 5,
+        None => 0,
+    };
+
+    base + edition
+}
+
+/// Money received for selling a joker (half its cost, at least $1)
+pub fn sell_value(joker_card: &JokerCard) -> u32 {
+    (joker_cost(joker_card, None) / 2).max(1)
+}
+
+fn default_money() -> u32 {
+    4
+}
+
+pub(crate) fn default_antes() -> usize {
+    8
+}
+
+pub(crate) fn default_hand_size() -> usize {
+    DEFAULT_HAND_SIZE
+}
+
+pub(crate) fn default_hands() -> u32 {
+    4
+}
+
+fn default_joker_slots() -> usize {
+    DEFAULT_JOKER_SLOTS
+}
+
+fn default_shop_slots() -> usize {
+    2
+}
+
+/// What the shop offers each time it is visited
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShopConfig {
+    /// Number of jokers offered per visit
+    #[serde(default = "default_shop_slots")]
+    pub slots: usize,
+    /// Jokers the shop draws its offers from (defaults to every joker)
+    #[serde(default)]
+    pub pool: Vec<JokerCard>,
+}
+
+impl Default for ShopConfig {
+    fn default() -> Self {
+        Self {
+            slots: default_shop_slots(),
+            pool: Vec::new(),
+        }
+    }
+}
+
+/// The built-in shop policies that can be selected from a run file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyConfig {
+    /// Buy the most expensive affordable jokers
+    #[default]
+    Greedy,
+    /// Buy jokers from a wishlist, in priority order
+    Scripted(Vec<JokerCard>),
+}
+
+impl PolicyConfig {
+    /// Creates the shop policy described by this configuration
+    pub fn create(&self) -> Box<dyn ShopPolicy> {
+        match self {
+            PolicyConfig::Greedy => Box::new(GreedyPolicy),
+            PolicyConfig::Scripted(wishlist) => Box::new(ScriptedPolicy {
+                wishlist: wishlist.clone(),
+            }),
+        }
+    }
+}
+
+/// Describes a full run to simulate
+///
+/// ```yaml
+/// seed: 42
+/// stake: red
+/// jokers:
+///   - Joker
+/// deck:
+///   variant: plasma
+///   add:
+///     - K♥ Gold
+///     - A♠ Gold Seal
+/// policy: greedy
+/// ```
+///
+/// A scripted policy lists the jokers to buy in priority order:
+/// ```yaml
+/// policy: !scripted [Blueprint, Baron]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunConfig {
+    /// Seed for every shuffle and shop offer in the run
+    #[serde(default)]
+    pub seed: u64,
+    /// Money at the start of the run
+    #[serde(default = "default_money")]
+    pub money: u32,
+    /// Stake the run is played at
+    #[serde(default)]
+    pub stake: Stake,
+    /// Number of antes to play
+    #[serde(default = "default_antes")]
+    pub antes: usize,
+    /// Number of cards dealt for each blind
+    #[serde(default = "default_hand_size")]
+    pub hand_size: usize,
+    /// Hands available per blind
+    #[serde(default = "default_hands")]
+    pub hands: u32,
+    /// Maximum number of jokers owned at once
+    #[serde(default = "default_joker_slots")]
+    pub joker_slots: usize,
+    /// Jokers owned at the start of the run
+    #[serde(default)]
+    pub jokers: Vec<JokerCard>,
+    #[serde(default)]
+    pub deck: DeckSpec,
+    #[serde(default)]
+    pub shop: ShopConfig,
+    #[serde(default)]
+    pub policy: PolicyConfig,
+}
+
+/// A joker for sale in the shop
+#[derive(Debug, Clone, Copy)]
+pub struct ShopOffer {
+    pub joker: JokerCard,
+    pub cost: u32,
+}
+
+/// A decision made by a shop policy
+///
+/// Indices refer to the shop offers and owned jokers as they are
+/// when the action is applied, so selling shifts later joker indices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShopAction {
+    /// Buy the offer at this index
+    Buy(usize),
+    /// Sell the owned joker at this index
+    Sell(usize),
+}
+
+/// Everything a shop policy can see when deciding what to do
+pub struct ShopView<'a> {
+    pub money: u32,
+    pub jokers: &'a [JokerCard],
+    pub joker_slots: usize,
+    pub offers: &'a [ShopOffer],
+}
+
+/// Decides which jokers to buy and sell when visiting the shop
+pub trait ShopPolicy {
+    fn shop(&mut self, view: &ShopView) -> Vec<ShopAction>;
+}
+
+/// Buys the most expensive jokers it can afford, selling its cheapest
+/// joker to make room when a pricier one is on offer
+pub struct GreedyPolicy;
+
+impl ShopPolicy for GreedyPolicy {
+    fn shop(&mut self, view: &ShopView) -> Vec<ShopAction> {
+        let mut actions = Vec::new();
+        let mut money = view.money;
+        let mut jokers = view.jokers.to_vec();
+
+        let by_cost =
+            (0..view.offers.len()).sorted_by_key(|&i| std::cmp::Reverse(view.offers[i].cost));
+        for index in by_cost {
+            let offer = view.offers[index];
+
+            if jokers.len() >= view.joker_slots {
+                // Make room only if the offer is worth more than the cheapest joker
+                let Some((cheapest, owned)) = jokers
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, joker)| joker_cost(joker, None))
+                else {
+                    continue;
+                };
+                if joker_cost(owned, None) >= offer.cost || money + sell_value(owned) < offer.cost {
+                    continue;
+                }
+                money += sell_value(owned);
+                jokers.remove(cheapest);
+                actions.push(ShopAction::Sell(cheapest));
+            }
+
+            if offer.cost <= money {
+                money -= offer.cost;
+                jokers.push(offer.joker);
+                actions.push(ShopAction::Buy(index));
+            }
+        }
+
+        actions
+    }
+}
+
+/// Buys jokers that appear on a wishlist, highest priority first
+pub struct ScriptedPolicy {
+    pub wishlist: Vec<JokerCard>,
+}
+
+impl ShopPolicy for ScriptedPolicy {
+    fn shop(&mut self, view: &ShopView) -> Vec<ShopAction> {
+        let mut actions = Vec::new();
+        let mut money = view.money;
+        let mut free_slots = view.joker_slots.saturating_sub(view.jokers.len());
+
+        for wanted in &self.wishlist {
+            let offer = view.offers.iter().enumerate().find(|(i, offer)| {
+                offer.joker.joker == wanted.joker && !actions.contains(&ShopAction::Buy(*i))
+            });
+            if let Some((index, offer)) = offer
+                && offer.cost <= money
+                && free_slots > 0
+            {
+                money -= offer.cost;
+                free_slots -= 1;
+                actions.push(ShopAction::Buy(index));
+            }
+        }
+
+        actions
+    }
+}
+
+/// The state of the run at the end of an ante
+#[derive(Debug, Clone)]
+pub struct AnteLog {
+    pub ante: usize,
+    pub money: u32,
+    pub jokers: Vec<JokerCard>,
+    /// Score of the hand played against each blind
+    pub scores: Vec<f64>,
+    /// Score needed to clear each blind
+    pub targets: Vec<f64>,
+}
+
+impl fmt::Display for AnteLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scores = self
+            .scores
+            .iter()
+            .zip(&self.targets)
+            .map(|(score, target)| format!("{}/{}", score, target))
+            .join(" | ");
+        let jokers = if self.jokers.is_empty() {
+            "none".to_string()
+        } else {
+            self.jokers.iter().join(", ")
+        };
+        write!(
+            f,
+            "Ante {}: ${} | scores {} | jokers {}",
+            self.ante, self.money, scores, jokers
+        )
+    }
+}
+
+/// The best play found for a dealt hand
+pub(crate) struct Play {
+    pub score: f64,
+    pub scoring_cards: Vec<Card>,
+    pub held: Vec<Card>,
+}
+
+/// Finds the play (up to five cards) that scores highest with the given jokers
+///
+/// `setup` prepares every game before it is scored, e.g. to set the deck variant.
+pub(crate) fn best_play(
+    hand: &[Card],
+    jokers: &[JokerCard],
+    setup: impl Fn(&mut GameState),
+) -> GameResult<Play> {
+    let mut best: Option<Play> = None;
+
+    for size in 1..=MAX_PLAYED_CARDS.min(hand.len()) {
+        for played in (0..hand.len()).combinations(size) {
+            let cards_played: Vec<Card> = played.iter().map(|&i| hand[i]).collect();
+            let held: Vec<Card> = (0..hand.len())
+                .filter(|i| !played.contains(i))
+                .map(|i| hand[i])
+                .collect();
+
+            let round = Round {
+                cards_played,
+                cards_held_in_hand: held.clone(),
+                jokers: jokers.to_vec(),
+            };
+            let mut game = GameState::new(round, false);
+            setup(&mut game);
+            let (chips, mult) = game.score()?;
+            let score = (chips * mult).floor();
+
+            if best.as_ref().is_none_or(|play| score > play.score) {
+                best = Some(Play {
+                    score,
+                    scoring_cards: game.scoring_cards,
+                    held,
+                });
+            }
+        }
+    }
+
+    best.ok_or_else(|| GameError::InvalidHand("no cards were dealt".to_string()))
+}
+
+/// A run in progress
+struct Run<'a> {
+    config: &'a RunConfig,
+    deck: Deck,
+    pool: Vec<JokerCard>,
+    rng: StdRng,
+    money: u32,
+    jokers: Vec<JokerCard>,
+}
+
+impl Run<'_> {
+    /// Deals a hand, plays the best hand against the blind and cashes out
+    fn play_blind(&mut self, blind: Blind) -> GameResult<f64> {
+        let mut deck = self.deck.clone();
+        deck.shuffle(self.rng.random());
+
+        let mut hand = Vec::new();
+        deck.draw_to_hand_size(&mut hand, self.config.hand_size);
+        let variant = self.config.deck.variant;
+        let play = best_play(&hand, &self.jokers, |game| {
+            game.card_extras = deck.card_extras().clone();
+            game.deck_variant = variant;
+        })?;
+
+        // Gold Seals pay out as soon as their card scores
+        let gold_seals = play
+            .scoring_cards
+            .iter()
+            .filter(|card| deck.extras(card).seal == Some(Seal::Gold))
+            .count() as u32;
+        self.money += gold_seals * GOLD_SEAL_MONEY;
+
+        // Cash out: interest is earned on the money held before the payout
+        let gold_cards = play
+            .held
+            .iter()
+            .filter(|card| deck.extras(card).enhancement == Some(ExtraEnhancement::Gold))
+            .count() as u32;
+        let interest = (self.money / INTEREST_STEP).min(MAX_INTEREST);
+        let remaining_hands = self.config.hands.saturating_sub(1);
+        let reward = self.config.stake.blind_reward(blind);
+        self.money += reward + remaining_hands + gold_cards * GOLD_CARD_MONEY + interest;
+
+        Ok(play.score)
+    }
+
+    /// Offers jokers from the pool and applies the policy's decisions
+    fn visit_shop(&mut self, policy: &mut dyn ShopPolicy) -> GameResult<()> {
+        let offers: Vec<ShopOffer> = (0..self.config.shop.slots)
+            .filter_map(|_| self.pool.choose(&mut self.rng))
+            .map(|joker| ShopOffer {
+                joker: *joker,
+                cost: joker_cost(joker, None),
+            })
+            .collect();
+
+        let view = ShopView {
+            money: self.money,
+            jokers: &self.jokers,
+            joker_slots: self.config.joker_slots,
+            offers: &offers,
+        };
+        let mut bought = vec![false; offers.len()];
+
+        for action in policy.shop(&view) {
+            match action {
+                ShopAction::Buy(index) => {
+                    let offer = offers
+                        .get(index)
+                        .filter(|_| !bought[index])
+                        .ok_or_else(|| {
+                            GameError::IllegalAction(format!(
+                                "shop offer {} is not available",
+                                index
+                            ))
+                        })?;
+                    if offer.cost > self.money || self.jokers.len() >= self.config.joker_slots {
+                        return Err(GameError::IllegalAction(format!(
+                            "cannot buy {} for ${} with ${} and {} of {} joker slots used",
+                            offer.joker,
+                            offer.cost,
+                            self.money,
+                            self.jokers.len(),
+                            self.config.joker_slots
+                        )));
+                    }
+                    self.money -= offer.cost;
+                    self.jokers.push(offer.joker);
+                    bought[index] = true;
+                }
+                ShopAction::Sell(index) => {
+                    if index >= self.jokers.len() {
+                        return Err(GameError::IllegalAction(format!(
+                            "cannot sell joker {} when only {} are owned",
+                            index,
+                            self.jokers.len()
+                        )));
+                    }
+                    let joker = self.jokers.remove(index);
+                    self.money += sell_value(&joker);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulates a full run, returning the state of the run at the end of each ante
+pub fn simulate_run(config: &RunConfig, policy: &mut dyn ShopPolicy) -> GameResult<Vec<AnteLog>> {
+    let pool = if config.shop.pool.is_empty() {
+        all::<Joker>()
+            .map(|joker| JokerCard::new(joker, None))
+            .collect()
+    } else {
+        config.shop.pool.clone()
+    };
+
+    let mut run = Run {
+        config,
+        deck: Deck::from_spec(&config.deck)?,
+        pool,
+        rng: StdRng::seed_from_u64(config.seed),
+        money: config.money,
+        jokers: config.jokers.clone(),
+    };
+
+    let mut log = Vec::new();
+    for ante in 1..=config.antes {
+        let mut scores = Vec::new();
+        let mut targets = Vec::new();
+        for blind in Blind::ALL {
+            scores.push(run.play_blind(blind)?);
+            targets.push(blind_target(ante, blind, config.stake, config.deck.variant));
+            run.visit_shop(policy)?;
+        }
+
+        log.push(AnteLog {
+            ante,
+            money: run.money,
+            jokers: run.jokers.clone(),
+            scores,
+            targets,
+        });
+    }
+
+    Ok(log)
+}
@@ -10,12 +10,21 @@ use std::fmt;
 pub enum GameError {
     /// Error indicating an invalid hand configuration with a descriptive message
     InvalidHand(String),
+    /// Error indicating an invalid deck composition with a descriptive message
+    InvalidDeck(String),
+    /// Error indicating a simulated player made a move the rules do not allow
+    IllegalAction(String),
+    /// Error indicating a consumable was used with invalid targets
+    InvalidConsumable(String),
 }
 
 impl fmt::Display for GameError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             GameError::InvalidHand(msg) => write!(f, "Invalid hand: {}", msg),
+            GameError::InvalidDeck(msg) => write!(f, "Invalid deck: {}", msg),
+            GameError::IllegalAction(msg) => write!(f, "Illegal action: {}", msg),
+            GameError::InvalidConsumable(msg) => write!(f, "Invalid consumable: {}", msg),
         }
     }
 }
@@ -0,0 +1,271 @@
+//! # Card Extras Module
+//!
+//! ortalib's `Card` only knows about the enhancements and editions used by the
+//! scoring rules. This module adds the card attributes ortalab needs beyond that
+//! (seals, the Gold and Stone enhancements, the Negative edition and the debuffed
+//! and face-down states) without
+//! changing the `Card` type itself.
+//!
+//! ## Key Components
+//! - `CardExtras`: The extra attributes of a single card
+//! - `CardSpec`: A card string that may carry extras, e.g. `K♠ Gold` or `A♥ Foil Red Seal`
+//! - `CardLabel`: How a card is shown in the explain output (face-down cards are hidden)
+//!
+//! Extras are stored alongside cards rather than inside them. Since a copied
+//! `Card` compares equal to its original, a card's extras can be looked up
+//! wherever the card ends up (deck, hand, played cards).
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use ortalib::Card;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::Deserialize;
+
+/// Seals that can be attached to a playing card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Seal {
+    /// Earns $3 when this card is played and scores
+    Gold,
+    /// Retriggers this card
+    Red,
+    /// Creates a Planet card if held at the end of the round
+    Blue,
+    /// Creates a Tarot card when discarded
+    Purple,
+}
+
+impl fmt::Display for Seal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Seal::Gold => "Gold",
+            Seal::Red => "Red",
+            Seal::Blue => "Blue",
+            Seal::Purple => "Purple",
+        };
+        write!(f, "{} Seal", name)
+    }
+}
+
+impl FromStr for Seal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Gold" => Ok(Seal::Gold),
+            "Red" => Ok(Seal::Red),
+            "Blue" => Ok(Seal::Blue),
+            "Purple" => Ok(Seal::Purple),
+            _ => Err(format!("Invalid Seal: `{}`", s)),
+        }
+    }
+}
+
+/// Enhancements that ortalib's `Enhancement` does not model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtraEnhancement {
+    /// Earns $3 if this card is held in hand at the end of the round
+    Gold,
+    /// +50 Chips; has no rank or suit, so it is left out of the poker hand,
+    /// always scores and triggers no OnScored jokers
+    Stone,
+}
+
+impl fmt::Display for ExtraEnhancement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtraEnhancement::Gold => write!(f, "Gold"),
+            ExtraEnhancement::Stone => write!(f, "Stone"),
+        }
+    }
+}
+
+impl FromStr for ExtraEnhancement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Gold" => Ok(ExtraEnhancement::Gold),
+            "Stone" => Ok(ExtraEnhancement::Stone),
+            _ => Err(format!("Invalid Enhancement: `{}`", s)),
+        }
+    }
+}
+
+/// Card attributes that are tracked by ortalab rather than ortalib
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CardExtras {
+    pub enhancement: Option<ExtraEnhancement>,
+    pub seal: Option<Seal>,
+    /// Negative edition: +1 hand size while the card is in hand
+    pub negative: bool,
+    /// Counts towards the poker hand, but gives no chips and triggers nothing
+    pub debuffed: bool,
+    /// Scores as normal, but is hidden in the explain output
+    pub face_down: bool,
+}
+
+/// Shown in the explain output in place of a face-down card
+const FACE_DOWN_LABEL: &str = "[face down]";
+
+/// How a card is shown in the explain output
+#[derive(Debug, Clone, Copy)]
+pub struct CardLabel<'a> {
+    card: &'a Card,
+    face_down: bool,
+    rank_and_suit_only: bool,
+}
+
+impl<'a> CardLabel<'a> {
+    pub fn new(card: &'a Card, extras: CardExtras) -> Self {
+        Self {
+            card,
+            face_down: extras.face_down,
+            rank_and_suit_only: false,
+        }
+    }
+
+    /// Shows only the rank and suit, leaving out enhancements and editions
+    pub fn rank_and_suit(self) -> Self {
+        Self {
+            rank_and_suit_only: true,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for CardLabel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.face_down {
+            write!(f, "{}", FACE_DOWN_LABEL)
+        } else if self.rank_and_suit_only {
+            write!(f, "{}{}", self.card.rank, self.card.suit)
+        } else {
+            write!(f, "{}", self.card)
+        }
+    }
+}
+
+/// A card together with its extras, parsed from a single card string
+///
+/// The extras are written after the regular ortalib card string:
+/// - `K♠ Gold`: a Gold King of Spades
+/// - `2♣ Stone`: a Stone card (its rank and suit are ignored)
+/// - `A♥ Foil Gold Seal`: a Foil Ace of Hearts with a Gold Seal
+/// - `Q♦ Steel Debuffed`: a debuffed Steel Queen of Diamonds
+/// - `J♣ Negative`: a Negative Jack of Clubs
+/// - `7♣ Face Down`: a face-down Seven of Clubs
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CardSpec {
+    pub card: Card,
+    pub extras: CardExtras,
+}
+
+impl FromStr for CardSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_ascii_whitespace().collect();
+        let mut extras = CardExtras::default();
+        let mut card_tokens = Vec::new();
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i];
+            if token == "Debuffed" {
+                extras.debuffed = true;
+                i += 1;
+                continue;
+            }
+            if token == "Negative" {
+                extras.negative = true;
+                i += 1;
+                continue;
+            }
+            if token == "Face" && tokens.get(i + 1) == Some(&"Down") {
+                extras.face_down = true;
+                i += 2;
+                continue;
+            }
+            if tokens.get(i + 1) == Some(&"Seal") {
+                if extras.seal.is_some() {
+                    return Err(format!("Card `{}` has more than one seal", s));
+                }
+                extras.seal = Some(token.parse()?);
+                i += 2;
+                continue;
+            }
+
+            match token.parse::<ExtraEnhancement>() {
+                Ok(enhancement) => extras.enhancement = Some(enhancement),
+                Err(_) => card_tokens.push(token),
+            }
+            i += 1;
+        }
+
+        let card: Card = card_tokens.join(" ").parse()?;
+        if card.enhancement.is_some() && extras.enhancement.is_some() {
+            return Err(format!("Card `{}` has more than one enhancement", s));
+        }
+        if card.edition.is_some() && extras.negative {
+            return Err(format!("Card `{}` has more than one edition", s));
+        }
+
+        Ok(CardSpec { card, extras })
+    }
+}
+
+impl TryFrom<String> for CardSpec {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl JsonSchema for CardSpec {
+    fn schema_name() -> Cow<'static, str> {
+        "Card".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A rank and suit, followed by any enhancement, edition (including \
+                `Negative`), seal and the `Debuffed` or `Face Down` states",
+            "pattern": "^(10|[2-9JQKA])[♠♥♣♦]( .+)?$",
+            "examples": ["K♠", "A♥ Foil Red Seal", "Q♦ Steel Debuffed", "7♣ Face Down"]
+        })
+    }
+}
+
+impl fmt::Display for CardSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card = self.card;
+        write!(f, "{}{}", card.rank, card.suit)?;
+        if let Some(enhancement) = card.enhancement {
+            write!(f, " {}", enhancement)?;
+        }
+        if let Some(enhancement) = self.extras.enhancement {
+            write!(f, " {}", enhancement)?;
+        }
+        if let Some(edition) = card.edition {
+            write!(f, " {}", edition)?;
+        }
+        if self.extras.negative {
+            write!(f, " Negative")?;
+        }
+        if let Some(seal) = self.extras.seal {
+            write!(f, " {}", seal)?;
+        }
+        if self.extras.debuffed {
+            write!(f, " Debuffed")?;
+        }
+        if self.extras.face_down {
+            write!(f, " Face Down")?;
+        }
+        Ok(())
+    }
+}
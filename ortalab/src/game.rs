@@ -8,42 +8,122 @@
 //!
 //! ## Scoring Process
 //! 1. Process initial joker flags and Blueprint effects
-//! 2. Identify the poker hand and set base chips/multiplier
-//! 3. Analyse hand conditions (pairs, straights, etc.) for joker effects
+//! 2. Evaluate the poker hand and set base chips/multiplier
+//! 3. Record hand conditions (pairs, straights, etc.) for joker effects
 //! 4. Determine which cards contribute to scoring
 //! 5. Process each scoring card individually
 //! 6. Process cards held in hand
 //! 7. Process independent joker effects
+//! 8. Roll for scored Glass cards shattering (only when a seed is set)
+//!
+//! ## Destruction Events
+//! Cards destroyed while playing a hand (Glass shattering, The Hanged Man)
+//! are recorded in `destroyed_cards`, and every joker is told about each one
+//! through `JokerEffect::on_card_destroyed`. Chance-based destruction needs a
+//! seed so results can be reproduced; without one no chance-based destruction
+//! happens and scoring matches the reference rules.
 //!
 //! ## Joker Processing
 //! - `process_on_scored_jokers()`: Handles jokers that activate when cards are scored
 //! - `process_on_held_jokers()`: Handles jokers that activate based on cards in hand
-//! - Special handling for retrigger effects (Mime, Sock and Buskin)
-
-use crate::errors::{GameError, GameResult};
-use crate::jokers;
-use crate::modifiers::{apply_edition, apply_enhancement, apply_steel_enhancement};
-use crate::poker::{analyse_hand_conditions, get_scoring_cards, identify_hand};
+//!
+//! ## Retriggers
+//! Jokers and seals ask for a card to be evaluated again by calling
+//! `request_retrigger()` while the card is processed (Mime, Sock and Buskin,
+//! Red Seal). Once the card's own evaluation is done, the queued retriggers for
+//! its phase are run one after another. A retrigger repeats the card's chips,
+//! enhancement and edition (scored) or its Steel bonus (held), then re-applies the
+//! jokers of that phase except the ones that asked for the retrigger. Requests
+//! made while retriggering are dropped, so retriggers never chain.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::balance::Balance;
+use crate::consumables::{self, Consumable, ConsumableUse};
+use crate::errors::GameResult;
+use crate::extras::{CardExtras, CardLabel, ExtraEnhancement, Seal};
+use crate::history::RunHistory;
+use crate::jokers::registry::CustomJoker;
+use crate::jokers::{self, JokerEffect, create_joker_effect};
+use crate::levels::HandLevels;
+use crate::modifiers::{apply_edition, apply_enhancement, apply_steel_enhancement, glass_shatters};
+use crate::poker::{self, evaluate_hand};
+use crate::variants::DeckVariant;
 
 use crate::explain_dbg_bool;
 
 // Import from external crates
 use ortalib::{Card, Chips, Enhancement, Joker, JokerCard, Mult, PokerHand, Rank, Round, Suit};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Chips a scored Stone card gives in place of its rank's chips
+pub const STONE_CHIPS: Chips = 50.0;
+
+/// Why a card was destroyed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DestructionCause {
+    /// A scored Glass card shattered after scoring
+    GlassShattered,
+    /// A consumable destroyed the card before scoring
+    Consumable(Consumable),
+}
+
+/// A card destroyed while playing the hand
+#[derive(Debug, Clone, Copy)]
+pub struct DestroyedCard {
+    pub card: Card,
+    pub cause: DestructionCause,
+}
+
+/// Which evaluation of a card a retrigger repeats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetriggerPhase {
+    /// Chips, enhancement, edition and "OnScored" jokers of a scored card
+    Scored,
+    /// Steel and "OnHeld" jokers of a card held in hand
+    Held,
+}
+
+/// What asked for a retrigger
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetriggerSource {
+    Joker(JokerCard),
+    RedSeal,
+}
+
+/// A request to evaluate the current card again
+#[derive(Debug, Clone, Copy)]
+pub struct Retrigger {
+    pub source: RetriggerSource,
+    pub phase: RetriggerPhase,
+    pub count: usize,
+}
 
 #[derive(Debug)]
 pub struct GameState {
-    pub round: Round,          // The round data (from ortalib)
-    pub chips: Chips,          // Current chip value during scoring
-    pub mult: Mult,            // Current multiplier during scoring
+    pub round: Round,                                   // The round data (from ortalib)
+    pub chips: Chips,                                   // Current chip value during scoring
+    pub mult: Mult,                                     // Current multiplier during scoring
     pub explain_enabled: bool, // Whether to track explain the scoring steps
+    pub card_extras: HashMap<Card, CardExtras>, // Extras of the cards that have any
+    pub custom_jokers: HashMap<JokerCard, CustomJoker>, // Registered jokers in the round
+    pub deck_variant: DeckVariant, // Deck the round is played with
+    pub history: RunHistory,   // Earlier hands of the run
+    pub hand_levels: HandLevels, // Levels of the poker hands
+    pub balance: Balance,      // Scoring constants of the round
+    pub deck: Option<Vec<Card>>, // Every card of the full deck, if known
+    pub remaining_deck: Option<Vec<Card>>, // Cards left to draw, if known
 
     // Poker hand analysis fields
-    pub scoring_cards: Vec<Card>, // Cards that contribute to the poker hand
-    pub contains_pair: bool,      // If the hand contains a pair
-    pub contains_two_pair: bool,  // If the hand contains two different pairs
+    pub poker_hand: Option<PokerHand>, // The played poker hand, once evaluated
+    pub scoring_cards: Vec<Card>,      // Cards that contribute to the poker hand
+    pub contains_pair: bool,           // If the hand contains a pair
+    pub contains_two_pair: bool,       // If the hand contains two different pairs
     pub contains_three_of_a_kind: bool, // If the hand contains three of a kind
-    pub contains_straight: bool,  // If the hand contains a straight
-    pub contains_flush: bool,     // If the hand contains a flush
+    pub contains_straight: bool,       // If the hand contains a straight
+    pub contains_flush: bool,          // If the hand contains a flush
 
     // Joker effect tracking fields
     pub four_fingers_active: bool,  // Four Fingers joker is active
@@ -53,15 +133,18 @@ pub struct GameState {
     pub smeared_joker_active: bool, // Smeared Joker is active
 
     // Retrigger tracking
-    pub mime_retriggers: usize, // Number of Mime retriggers to apply
-    pub sock_and_buskin_retriggers: usize, // Number of Sock and Buskin retriggers
+    pub retriggers: Vec<Retrigger>, // Retriggers requested for the current card
 
     // Used for tracking Photograph joker
     pub first_face_card_processed: bool,
 
     // Blueprint tracking
-    pub blueprint_copied_jokers: Vec<(JokerCard, Joker)>, // For OnScored jokers
-    pub blueprint_held_jokers: Vec<(JokerCard, Joker)>,   // For OnHeld jokers
+    pub blueprint_copied_jokers: Vec<(JokerCard, JokerCard)>, // For OnScored jokers
+    pub blueprint_held_jokers: Vec<(JokerCard, JokerCard)>,   // For OnHeld jokers
+
+    // Destruction tracking
+    pub rng: Option<StdRng>, // Seeded RNG for chance-based effects, if any
+    pub destroyed_cards: Vec<DestroyedCard>, // Cards destroyed so far, in order
 }
 
 impl GameState {
@@ -71,7 +154,16 @@ impl GameState {
             chips: 0.0,
             mult: 0.0,
             explain_enabled: explain,
-
+            card_extras: HashMap::new(),
+            custom_jokers: HashMap::new(),
+            deck_variant: DeckVariant::default(),
+            history: RunHistory::default(),
+            hand_levels: HandLevels::default(),
+            balance: Balance::default(),
+            deck: None,
+            remaining_deck: None,
+
+            poker_hand: None,
             scoring_cards: Vec::new(),
             contains_pair: false,
             contains_two_pair: false,
@@ -85,86 +177,172 @@ impl GameState {
             splash_active: false,
             smeared_joker_active: false,
 
-            mime_retriggers: 0,
-            sock_and_buskin_retriggers: 0,
+            retriggers: Vec::new(),
             first_face_card_processed: false,
             blueprint_copied_jokers: Vec::new(),
             blueprint_held_jokers: Vec::new(),
+
+            rng: None,
+            destroyed_cards: Vec::new(),
         }
     }
 
-    /// Process "OnScored" jokers for a specific card
-    fn process_on_scored_jokers(&mut self, card: &Card) -> GameResult<()> {
-        for joker_card in &self.round.jokers.clone() {
-            let effect = jokers::create_joker_effect(joker_card.joker);
-            if effect.activation_type() == jokers::ActivationType::OnScored
-                && effect.can_apply(self)
-            {
-                effect.apply(self, joker_card, card)?;
-            }
+    /// The extras of a played or held card
+    pub fn extras(&self, card: &Card) -> CardExtras {
+        self.card_extras.get(card).copied().unwrap_or_default()
+    }
+
+    /// Stone cards have no rank or suit
+    pub fn is_stone(&self, card: &Card) -> bool {
+        self.extras(card).enhancement == Some(ExtraEnhancement::Stone)
+    }
+
+    /// Chips a scored card gives before its enhancement and edition, and how it is shown
+    fn base_chips(&self, card: &Card, label: CardLabel) -> (Chips, String) {
+        if self.is_stone(card) {
+            let shown = if self.extras(card).face_down {
+                label.to_string()
+            } else {
+                format!("{} Stone", label)
+            };
+            (self.balance.enhancements.stone, shown)
+        } else {
+            (card.rank.rank_value(), label.to_string())
         }
+    }
 
-        // Process Blueprint-copied OnScored jokers
-        for (blueprint_card, copied_joker) in &self.blueprint_copied_jokers.clone() {
-            let effect = jokers::create_joker_effect(*copied_joker);
-            if effect.can_apply(self) {
-                effect.apply(self, blueprint_card, card)?;
-            }
+    /// Whether a card counts as a suit, the way the poker hand was formed
+    ///
+    /// Wild cards count as every suit, Smeared Joker makes cards count as the
+    /// other suit of their color too, and Stone cards have no suit.
+    pub fn counts_as_suit(&self, card: &Card, suit: Suit) -> bool {
+        !self.is_stone(card) && poker::counts_as_suit(card, suit, self.smeared_joker_active)
+    }
+
+    /// Debuffed cards count towards the poker hand, but give no chips and trigger nothing
+    pub fn is_debuffed(&self, card: &Card) -> bool {
+        self.extras(card).debuffed
+    }
+
+    /// How a card is shown in the explain output
+    pub fn label<'a>(&self, card: &'a Card) -> CardLabel<'a> {
+        CardLabel::new(card, self.extras(card))
+    }
+
+    /// The effect of a joker in the round, built-in or registered
+    pub fn joker_effect(&self, joker_card: &JokerCard) -> Arc<dyn JokerEffect> {
+        match self.custom_jokers.get(joker_card) {
+            Some(custom) => custom.effect.clone(),
+            None => create_joker_effect(joker_card.joker).into(),
         }
+    }
 
-        // Handle Sock and Buskin retriggers
-        let retrigger_count = self.sock_and_buskin_retriggers;
-        if retrigger_count > 0 && (self.pareidolia_active || card.rank.is_face()) {
-            // Clear the retrigger counter to prevent infinite loops
-            self.sock_and_buskin_retriggers = 0;
-            // With retriggers, can reapply Photograph on the same card
-            self.first_face_card_processed = false;
+    /// How a joker is named in the explain output
+    pub fn joker_name(&self, joker_card: &JokerCard) -> String {
+        match self.custom_jokers.get(joker_card) {
+            Some(custom) => custom.name.to_string(),
+            None => joker_card.joker.to_string(),
+        }
+    }
+
+    /// Enables chance-based effects, such as Glass cards shattering
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+    }
+
+    /// Applies Tarot and Spectral cards to the round before it is scored
+    pub fn use_consumables(&mut self, uses: &[ConsumableUse]) -> GameResult<()> {
+        let destroyed = consumables::apply_consumables(
+            &mut self.round,
+            &mut self.card_extras,
+            uses,
+            self.explain_enabled,
+        )?;
+        for (card, consumable) in destroyed {
+            self.destroy_card(card, DestructionCause::Consumable(consumable))?;
+        }
+        Ok(())
+    }
 
-            // Apply retriggers
-            for _ in 0..retrigger_count {
-                // Re-apply the card's base chips
-                let rank_chips: f64 = card.rank.rank_value();
-                self.chips += rank_chips;
+    /// Records a destroyed card and lets every joker react to it
+    fn destroy_card(&mut self, card: Card, cause: DestructionCause) -> GameResult<()> {
+        let destroyed = DestroyedCard { card, cause };
+        self.destroyed_cards.push(destroyed);
 
+        match destroyed.cause {
+            DestructionCause::GlassShattered => {
                 explain_dbg_bool!(
                     self.explain_enabled,
-                    "Retrigger: {} +{} Chips ({} x {})",
-                    card,
-                    rank_chips,
-                    self.chips,
-                    self.mult
+                    "{} shattered",
+                    self.label(&destroyed.card)
                 );
+            }
+            DestructionCause::Consumable(consumable) => {
+                explain_dbg_bool!(
+                    self.explain_enabled,
+                    "{} {} destroyed",
+                    consumable,
+                    self.label(&destroyed.card)
+                );
+            }
+        }
 
-                // Re-apply card enhancements and editions
-                if card.enhancement.is_some() {
-                    apply_enhancement(card, &mut self.chips, &mut self.mult, self.explain_enabled)?;
-                }
+        for joker_card in &self.round.jokers.clone() {
+            let effect = self.joker_effect(joker_card);
+            effect.on_card_destroyed(self, joker_card, &destroyed)?;
+        }
+        Ok(())
+    }
 
-                if card.edition.is_some() {
-                    apply_edition(card, &mut self.chips, &mut self.mult, self.explain_enabled)?;
+    /// Process "OnScored" jokers for a specific card
+    fn process_on_scored_jokers(&mut self, card: &Card) -> GameResult<()> {
+        // Debuffed cards trigger no jokers
+        if self.is_debuffed(card) {
+            return Ok(());
+        }
+
+        // Stone cards have no rank or suit for the jokers to look at
+        if !self.is_stone(card) {
+            for joker_card in &self.round.jokers.clone() {
+                let effect = self.joker_effect(joker_card);
+                if effect.activation_type() == jokers::ActivationType::OnScored
+                    && effect.can_apply(self)
+                {
+                    effect.apply(self, joker_card, card)?;
                 }
+            }
 
-                // Re-apply "OnScored" jokers but exclude Sock and Buskin to prevent infinite loops
-                for joker_card in &self.round.jokers.clone() {
-                    if joker_card.joker != Joker::SockAndBuskin {
-                        let effect = jokers::create_joker_effect(joker_card.joker);
-                        if effect.activation_type() == jokers::ActivationType::OnScored
-                            && effect.can_apply(self)
-                        {
-                            effect.apply(self, joker_card, card)?;
-                        }
-                    }
+            // Process Blueprint-copied OnScored jokers
+            for (blueprint_card, copied_joker) in &self.blueprint_copied_jokers.clone() {
+                let effect = self.joker_effect(copied_joker);
+                if effect.can_apply(self) {
+                    effect.apply(self, blueprint_card, card)?;
                 }
             }
         }
-        Ok(())
+
+        // A Red Seal retriggers the card itself
+        if self.extras(card).seal == Some(Seal::Red) {
+            self.request_retrigger(RetriggerSource::RedSeal, RetriggerPhase::Scored, 1);
+            explain_dbg_bool!(
+                self.explain_enabled,
+                "Red Seal retriggers {}",
+                self.label(card)
+            );
+        }
+        self.run_retriggers(card, RetriggerPhase::Scored)
     }
 
     /// Process "OnHeld" jokers for a specific card
     fn process_on_held_jokers(&mut self, card: &Card) -> GameResult<()> {
+        // Debuffed cards trigger no jokers
+        if self.is_debuffed(card) {
+            return Ok(());
+        }
+
         // Get applicable jokers
         for joker_card in &self.round.jokers.clone() {
-            let effect = jokers::create_joker_effect(joker_card.joker);
+            let effect = self.joker_effect(joker_card);
             if effect.activation_type() == jokers::ActivationType::OnHeld && effect.can_apply(self)
             {
                 // Special handling for Raised Fist:
@@ -188,13 +366,12 @@ impl GameState {
 
                         let right_most = lowest_cards.last();
 
-                        if let Some(right_most_card) = right_most {
-                            if right_most_card.rank == card.rank
-                                && right_most_card.suit == card.suit
-                                && right_most_card.enhancement == card.enhancement
-                            {
-                                effect.apply(self, joker_card, card)?;
-                            }
+                        if let Some(right_most_card) = right_most
+                            && right_most_card.rank == card.rank
+                            && right_most_card.suit == card.suit
+                            && right_most_card.enhancement == card.enhancement
+                        {
+                            effect.apply(self, joker_card, card)?;
                         }
                     }
                 } else {
@@ -205,10 +382,10 @@ impl GameState {
 
         // Process Blueprint-copied OnHeld jokers
         for (blueprint_card, copied_joker) in &self.blueprint_held_jokers.clone() {
-            let effect = jokers::create_joker_effect(*copied_joker);
+            let effect = self.joker_effect(copied_joker);
 
             // Special handling for Raised Fist
-            if *copied_joker == Joker::RaisedFist {
+            if copied_joker.joker == Joker::RaisedFist {
                 // Find the cards with the lowest rank in hand
                 let lowest_rank = self
                     .round
@@ -228,54 +405,163 @@ impl GameState {
             }
         }
 
-        // Handle Mime retriggers
-        let retrigger_count = self.mime_retriggers;
-        if retrigger_count > 0 {
-            // Clear the retrigger counter to prevent infinite loops
-            self.mime_retriggers = 0;
-
-            // Apply retriggers
-            for _ in 0..retrigger_count {
-                // Re-apply Steel enhancement if present
-                if let Some(Enhancement::Steel) = &card.enhancement {
-                    apply_steel_enhancement(
-                        card,
-                        &mut self.chips,
-                        &mut self.mult,
-                        self.explain_enabled,
-                    )?;
+        // A Red Seal retriggers the card itself
+        if self.extras(card).seal == Some(Seal::Red) {
+            self.request_retrigger(RetriggerSource::RedSeal, RetriggerPhase::Held, 1);
+            explain_dbg_bool!(
+                self.explain_enabled,
+                "Red Seal retriggers {}",
+                self.label(card)
+            );
+        }
+        self.run_retriggers(card, RetriggerPhase::Held)
+    }
+
+    /// Asks for the current card to be evaluated `count` more times
+    pub fn request_retrigger(
+        &mut self,
+        source: RetriggerSource,
+        phase: RetriggerPhase,
+        count: usize,
+    ) {
+        self.retriggers.push(Retrigger {
+            source,
+            phase,
+            count,
+        });
+    }
+
+    /// Runs the retriggers queued for the current card
+    fn run_retriggers(&mut self, card: &Card, phase: RetriggerPhase) -> GameResult<()> {
+        let queued: Vec<Retrigger> = std::mem::take(&mut self.retriggers)
+            .into_iter()
+            .filter(|retrigger| retrigger.phase == phase)
+            .collect();
+        let count: usize = queued.iter().map(|retrigger| retrigger.count).sum();
+        if count == 0 {
+            return Ok(());
+        }
+
+        // Jokers that asked for a retrigger are not re-applied
+        let excluded: Vec<JokerCard> = queued
+            .iter()
+            .filter_map(|retrigger| match retrigger.source {
+                RetriggerSource::Joker(joker_card) => Some(joker_card),
+                RetriggerSource::RedSeal => None,
+            })
+            .collect();
+
+        if phase == RetriggerPhase::Scored {
+            // With retriggers, can reapply Photograph on the same card
+            self.first_face_card_processed = false;
+        }
+
+        for _ in 0..count {
+            match phase {
+                RetriggerPhase::Scored => self.retrigger_scored(card, &excluded)?,
+                RetriggerPhase::Held => self.retrigger_held(card, &excluded)?,
+            }
+        }
+
+        // Retriggers never chain
+        self.retriggers.clear();
+        Ok(())
+    }
+
+    /// Evaluates a scored card again
+    fn retrigger_scored(&mut self, card: &Card, excluded: &[JokerCard]) -> GameResult<()> {
+        // Re-apply the card's base chips
+        let (base_chips, shown) = self.base_chips(card, self.label(card));
+        self.chips += base_chips;
+
+        explain_dbg_bool!(
+            self.explain_enabled,
+            "Retrigger: {} +{} Chips ({} x {})",
+            shown,
+            base_chips,
+            self.chips,
+            self.mult
+        );
+
+        // Re-apply card enhancements and editions
+        let label = self.label(card);
+        if card.enhancement.is_some() {
+            apply_enhancement(
+                card,
+                label,
+                &self.balance.enhancements,
+                &mut self.chips,
+                &mut self.mult,
+                self.explain_enabled,
+            )?;
+        }
+
+        if card.edition.is_some() {
+            apply_edition(
+                card,
+                label,
+                &self.balance.editions,
+                &mut self.chips,
+                &mut self.mult,
+                self.explain_enabled,
+            )?;
+        }
+
+        // Re-apply "OnScored" jokers
+        for joker_card in &self.round.jokers.clone() {
+            if !excluded.contains(joker_card) && !self.is_stone(card) {
+                let effect = self.joker_effect(joker_card);
+                if effect.activation_type() == jokers::ActivationType::OnScored
+                    && effect.can_apply(self)
+                {
+                    effect.apply(self, joker_card, card)?;
                 }
+            }
+        }
+        Ok(())
+    }
 
-                // Re-apply "OnHeld" jokers but exclude Mime to prevent infinite loops
-                for joker_card in &self.round.jokers.clone() {
-                    if joker_card.joker != Joker::Mime {
-                        let effect = jokers::create_joker_effect(joker_card.joker);
-                        if effect.activation_type() == jokers::ActivationType::OnHeld
-                            && effect.can_apply(self)
+    /// Evaluates a card held in hand again
+    fn retrigger_held(&mut self, card: &Card, excluded: &[JokerCard]) -> GameResult<()> {
+        // Re-apply Steel enhancement if present
+        if let Some(Enhancement::Steel) = &card.enhancement {
+            apply_steel_enhancement(
+                card,
+                self.label(card),
+                &self.balance.enhancements,
+                &mut self.chips,
+                &mut self.mult,
+                self.explain_enabled,
+            )?;
+        }
+
+        // Re-apply "OnHeld" jokers
+        for joker_card in &self.round.jokers.clone() {
+            if !excluded.contains(joker_card) {
+                let effect = self.joker_effect(joker_card);
+                if effect.activation_type() == jokers::ActivationType::OnHeld
+                    && effect.can_apply(self)
+                {
+                    // Special handling for Raised Fist
+                    if joker_card.joker == Joker::RaisedFist {
+                        let lowest_rank = self
+                            .round
+                            .cards_held_in_hand
+                            .iter()
+                            .min_by_key(|c| c.rank)
+                            .map(|c| c.rank);
+
+                        if let Some(lowest) = lowest_rank
+                            && card.rank == lowest
                         {
-                            // Special handling for Raised Fist
-                            if joker_card.joker == Joker::RaisedFist {
-                                let lowest_rank = self
-                                    .round
-                                    .cards_held_in_hand
-                                    .iter()
-                                    .min_by_key(|c| c.rank)
-                                    .map(|c| c.rank);
-
-                                if let Some(lowest) = lowest_rank {
-                                    if card.rank == lowest {
-                                        effect.apply(self, joker_card, card)?;
-                                    }
-                                }
-                            } else {
-                                effect.apply(self, joker_card, card)?;
-                            }
+                            effect.apply(self, joker_card, card)?;
                         }
+                    } else {
+                        effect.apply(self, joker_card, card)?;
                     }
                 }
             }
         }
-
         Ok(())
     }
 
@@ -312,44 +598,68 @@ impl GameState {
             .iter()
             .any(|joker_card| joker_card.joker == Joker::SmearedJoker);
         self.first_face_card_processed = false;
-        self.mime_retriggers = 0;
-        self.sock_and_buskin_retriggers = 0;
+        self.retriggers.clear();
+        self.poker_hand = None;
 
         // Process Blueprint jokers
         for joker_card in &self.round.jokers.clone() {
             if joker_card.joker == Joker::Blueprint {
-                let effect = jokers::create_joker_effect(joker_card.joker);
+                let effect = self.joker_effect(joker_card);
                 let placeholder_card = Card::new(Rank::Ace, Suit::Diamonds, None, None);
                 effect.apply(self, joker_card, &placeholder_card)?;
             }
         }
 
-        // Step 2: Identify the poker hand
-        let poker_hand: PokerHand = identify_hand(
-            &self.round.cards_played,
+        // Step 2: Evaluate the poker hand, which Stone cards are no part of
+        let ranked_cards: Vec<Card> = self
+            .round
+            .cards_played
+            .iter()
+            .filter(|card| !self.is_stone(card))
+            .copied()
+            .collect();
+        let evaluation = evaluate_hand(
+            &ranked_cards,
             self.four_fingers_active,
             self.shortcut_active,
             self.smeared_joker_active,
-        )
-        .map_err(|e| GameError::InvalidHand(e.to_string()))?;
-        let (base_chips, base_mult) = poker_hand.hand_value();
+        );
+        let poker_hand = evaluation.hand;
+        self.poker_hand = Some(poker_hand);
+        let (base_chips, base_mult) = self
+            .hand_levels
+            .hand_value(poker_hand, self.balance.hand_value(poker_hand));
         self.chips = base_chips;
         self.mult = base_mult;
-        explain_dbg_bool!(
-            self.explain_enabled,
-            "{:?} ({} x {})",
-            poker_hand,
-            base_chips,
-            base_mult
-        );
+        match self.hand_levels.level(poker_hand) {
+            1 => explain_dbg_bool!(
+                self.explain_enabled,
+                "{:?} ({} x {})",
+                poker_hand,
+                base_chips,
+                base_mult
+            ),
+            level => explain_dbg_bool!(
+                self.explain_enabled,
+                "{:?} lvl.{} ({} x {})",
+                poker_hand,
+                level,
+                base_chips,
+                base_mult
+            ),
+        }
+        for assignment in &evaluation.suit_assignments {
+            explain_dbg_bool!(
+                self.explain_enabled,
+                "{} counted as {} for {}",
+                self.label(&assignment.card),
+                assignment.suit,
+                poker_hand
+            );
+        }
 
-        // Step 3: Analyse hand conditions for joker effects
-        let conditions = analyse_hand_conditions(
-            &self.round.cards_played,
-            self.four_fingers_active,
-            self.shortcut_active,
-            self.smeared_joker_active,
-        )?;
+        // Step 3: Record hand conditions for joker effects
+        let conditions = evaluation.conditions;
         self.contains_pair = conditions.contains_pair;
         self.contains_two_pair = conditions.contains_two_pair;
         self.contains_three_of_a_kind = conditions.contains_three_of_a_kind;
@@ -360,39 +670,60 @@ impl GameState {
         self.scoring_cards = if self.splash_active {
             // With Splash joker, all played cards score
             self.round.cards_played.to_vec()
+        } else if ranked_cards.len() < self.round.cards_played.len() {
+            // Stone cards always score, in the order they were played
+            self.round
+                .cards_played
+                .iter()
+                .filter(|card| self.is_stone(card) || evaluation.scoring_cards.contains(card))
+                .copied()
+                .collect()
         } else {
-            get_scoring_cards(
-                &poker_hand,
-                &self.round.cards_played,
-                self.four_fingers_active,
-                self.shortcut_active,
-                self.smeared_joker_active,
-            )
+            evaluation.scoring_cards
         };
 
         // Step 5: Process each card separately
         for card in self.scoring_cards.clone() {
-            let rank_chips: f64 = card.rank.rank_value();
-            self.chips += rank_chips;
+            let label = self.label(&card);
+            if self.is_debuffed(&card) {
+                explain_dbg_bool!(self.explain_enabled, "{} is debuffed", label);
+                continue;
+            }
+
+            let (base_chips, shown) = self.base_chips(&card, label.rank_and_suit());
+            self.chips += base_chips;
 
             explain_dbg_bool!(
                 self.explain_enabled,
-                "{}{} +{} Chips ({} x {})",
-                card.rank,
-                card.suit,
-                rank_chips,
+                "{} +{} Chips ({} x {})",
+                shown,
+                base_chips,
                 self.chips,
                 self.mult
             );
 
             // Apply card enhancements if present
             if card.enhancement.is_some() {
-                apply_enhancement(&card, &mut self.chips, &mut self.mult, self.explain_enabled)?;
+                apply_enhancement(
+                    &card,
+                    label,
+                    &self.balance.enhancements,
+                    &mut self.chips,
+                    &mut self.mult,
+                    self.explain_enabled,
+                )?;
             }
 
             // Apply card editions if present
             if card.edition.is_some() {
-                apply_edition(&card, &mut self.chips, &mut self.mult, self.explain_enabled)?;
+                apply_edition(
+                    &card,
+                    label,
+                    &self.balance.editions,
+                    &mut self.chips,
+                    &mut self.mult,
+                    self.explain_enabled,
+                )?;
             }
             // Process "OnScored" jokers for this card
             self.process_on_scored_jokers(&card)?;
@@ -400,9 +731,13 @@ impl GameState {
 
         // Step 6: Process cards held in hand
         for card in self.round.cards_held_in_hand.clone() {
-            if let Some(Enhancement::Steel) = &card.enhancement {
+            if let Some(Enhancement::Steel) = &card.enhancement
+                && !self.is_debuffed(&card)
+            {
                 apply_steel_enhancement(
                     &card,
+                    self.label(&card),
+                    &self.balance.enhancements,
                     &mut self.chips,
                     &mut self.mult,
                     self.explain_enabled,
@@ -415,6 +750,28 @@ impl GameState {
         // Step 7: Process jokers (independent activation)
         jokers::process_jokers(self)?;
 
+        // Step 8: The deck may change the final Chips and Mult
+        if self.deck_variant.balance(&mut self.chips, &mut self.mult) {
+            explain_dbg_bool!(
+                self.explain_enabled,
+                "{} balances Chips and Mult ({} x {})",
+                self.deck_variant,
+                self.chips,
+                self.mult
+            );
+        }
+
+        // Step 9: Scored Glass cards may shatter once scoring is finished
+        for card in self.scoring_cards.clone() {
+            if card.enhancement == Some(Enhancement::Glass)
+                && !self.is_debuffed(&card)
+                && let Some(rng) = self.rng.as_mut()
+                && glass_shatters(rng)
+            {
+                self.destroy_card(card, DestructionCause::GlassShattered)?;
+            }
+        }
+
         Ok((self.chips, self.mult))
     }
 }
@@ -0,0 +1,118 @@
+//! # History Module
+//!
+//! This module describes what happened earlier in the run, for jokers whose
+//! effect depends on it (Supernova, Card Sharp, Obelisk, Ride the Bus).
+//!
+//! A round file may include a `history` section; without one the round is
+//! treated as the first hand of the run:
+//!
+//! ```yaml
+//! history:
+//!   hands_played:
+//!     Pair: 4
+//!     Flush: 2
+//!   hands_this_round: [Pair]
+//!   consecutive_non_face_hands: 3
+//!   hands_without_most_played: 1
+//! ```
+//!
+//! Every count is for the hands before the one being scored.
+//!
+//! ## Key Components
+//! - `RunHistory`: The `history` section of a round file
+//! - `HandName`: A poker hand written by name, e.g. `Three Of A Kind`
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use enum_iterator::all;
+use itertools::Itertools;
+use ortalib::PokerHand;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::Deserialize;
+
+/// A poker hand as written in a round file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct HandName(pub PokerHand);
+
+impl FromStr for HandName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all::<PokerHand>()
+            .find(|hand| hand.to_string() == s)
+            .map(HandName)
+            .ok_or_else(|| format!("Invalid PokerHand: `{}`", s))
+    }
+}
+
+impl TryFrom<String> for HandName {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for HandName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl JsonSchema for HandName {
+    fn schema_name() -> Cow<'static, str> {
+        "PokerHand".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        let hands = all::<PokerHand>()
+            .map(|hand| hand.to_string())
+            .collect_vec();
+        json_schema!({
+            "type": "string",
+            "description": "A poker hand",
+            "enum": hands
+        })
+    }
+}
+
+/// What happened in the run before the hand being scored
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RunHistory {
+    /// Number of times each poker hand was played this run
+    pub hands_played: HashMap<HandName, u32>,
+    /// Poker hands played earlier this round, in order
+    pub hands_this_round: Vec<HandName>,
+    /// Hands played in a row without a scoring face card (Ride the Bus)
+    pub consecutive_non_face_hands: u32,
+    /// Hands played in a row without playing the most played poker hand (Obelisk)
+    pub hands_without_most_played: u32,
+}
+
+impl RunHistory {
+    /// Number of times `hand` was played this run, before this hand
+    pub fn times_played(&self, hand: PokerHand) -> u32 {
+        self.hands_played
+            .get(&HandName(hand))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether `hand` was already played this round
+    pub fn played_this_round(&self, hand: PokerHand) -> bool {
+        self.hands_this_round.contains(&HandName(hand))
+    }
+
+    /// Whether `hand`, once played, is played more often than every other hand
+    pub fn is_most_played(&self, hand: PokerHand) -> bool {
+        let played = self.times_played(hand) + 1;
+        all::<PokerHand>()
+            .filter(|&other| other != hand)
+            .all(|other| self.times_played(other) < played)
+    }
+}
@@ -0,0 +1,80 @@
+//! # Input Module
+//!
+//! This module reads ortalab's input files in any of the supported formats.
+//!
+//! Round files, deck compositions, run descriptions and balance files can be
+//! written as YAML, JSON, RON or TOML. The format is taken from the file
+//! extension (`.yml`/`.yaml`, `.json`, `.ron`, `.toml`). Files without a known
+//! extension, including standard input, are detected from their content: JSON
+//! starts with `{`, RON with `(`, and anything else is read as YAML.
+//!
+//! ## Key Components
+//! - `InputFormat`: The supported formats and their detection
+//! - `parse_input()`: Deserializes a file's contents in its detected format
+
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+/// A format input files can be written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    Yaml,
+    Json,
+    Ron,
+    Toml,
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InputFormat::Yaml => "YAML",
+            InputFormat::Json => "JSON",
+            InputFormat::Ron => "RON",
+            InputFormat::Toml => "TOML",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl InputFormat {
+    /// The format of a file with this extension, if it is a known one
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "yml" | "yaml" => Some(InputFormat::Yaml),
+            "json" => Some(InputFormat::Json),
+            "ron" => Some(InputFormat::Ron),
+            "toml" => Some(InputFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// The format of some contents, guessed from their first character
+    pub fn from_content(contents: &str) -> Self {
+        match contents.trim_start().chars().next() {
+            Some('{') => InputFormat::Json,
+            Some('(') => InputFormat::Ron,
+            _ => InputFormat::Yaml,
+        }
+    }
+
+    /// The format of a file, from its extension or else from its contents
+    pub fn detect(path: &Path, contents: &str) -> Self {
+        Self::from_extension(path).unwrap_or_else(|| Self::from_content(contents))
+    }
+}
+
+/// Deserializes the contents of `path` in its detected format
+pub fn parse_input<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T, Box<dyn Error>> {
+    let format = InputFormat::detect(path, contents);
+    let value: Result<T, Box<dyn Error>> = match format {
+        InputFormat::Yaml => serde_yaml::from_str(contents).map_err(Into::into),
+        InputFormat::Json => serde_json::from_str(contents).map_err(Into::into),
+        InputFormat::Ron => ron::from_str(contents).map_err(Into::into),
+        InputFormat::Toml => toml::from_str(contents).map_err(Into::into),
+    };
+    value.map_err(|err| format!("invalid {} in `{}`: {}", format, path.display(), err).into())
+}
@@ -21,10 +21,11 @@ impl JokerEffect for Joker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult += 4.0;
+        let mult_increase = game_state.balance.joker_value("Joker", 4.0);
+        game_state.mult += mult_increase;
         let message = format!(
-            "{} +4 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Mult ({} x {})",
+            joker_card.joker, mult_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -49,10 +50,11 @@ impl JokerEffect for JollyJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult += 8.0;
+        let mult_increase = game_state.balance.joker_value("Jolly Joker", 8.0);
+        game_state.mult += mult_increase;
         let message = format!(
-            "{} +8 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Mult ({} x {})",
+            joker_card.joker, mult_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -77,10 +79,11 @@ impl JokerEffect for ZanyJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult += 12.0;
+        let mult_increase = game_state.balance.joker_value("Zany Joker", 12.0);
+        game_state.mult += mult_increase;
         let message = format!(
-            "{} +12 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Mult ({} x {})",
+            joker_card.joker, mult_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -105,10 +108,11 @@ impl JokerEffect for MadJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult += 10.0;
+        let mult_increase = game_state.balance.joker_value("Mad Joker", 10.0);
+        game_state.mult += mult_increase;
         let message = format!(
-            "{} +10 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Mult ({} x {})",
+            joker_card.joker, mult_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -133,10 +137,11 @@ impl JokerEffect for CrazyJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult += 12.0;
+        let mult_increase = game_state.balance.joker_value("Crazy Joker", 12.0);
+        game_state.mult += mult_increase;
         let message = format!(
-            "{} +12 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Mult ({} x {})",
+            joker_card.joker, mult_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -161,10 +166,11 @@ impl JokerEffect for DrollJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult += 10.0;
+        let mult_increase = game_state.balance.joker_value("Droll Joker", 10.0);
+        game_state.mult += mult_increase;
         let message = format!(
-            "{} +10 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Mult ({} x {})",
+            joker_card.joker, mult_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -189,10 +195,11 @@ impl JokerEffect for SlyJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.chips += 50.0;
+        let chips_increase = game_state.balance.joker_value("Sly Joker", 50.0);
+        game_state.chips += chips_increase;
         let message = format!(
-            "{} +50 Chips ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Chips ({} x {})",
+            joker_card.joker, chips_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -217,10 +224,11 @@ impl JokerEffect for WilyJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.chips += 100.0;
+        let chips_increase = game_state.balance.joker_value("Wily Joker", 100.0);
+        game_state.chips += chips_increase;
         let message = format!(
-            "{} +100 Chips ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Chips ({} x {})",
+            joker_card.joker, chips_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -245,10 +253,11 @@ impl JokerEffect for CleverJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.chips += 80.0;
+        let chips_increase = game_state.balance.joker_value("Clever Joker", 80.0);
+        game_state.chips += chips_increase;
         let message = format!(
-            "{} +80 Chips ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Chips ({} x {})",
+            joker_card.joker, chips_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -273,10 +282,11 @@ impl JokerEffect for DeviousJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.chips += 100.0;
+        let chips_increase = game_state.balance.joker_value("Devious Joker", 100.0);
+        game_state.chips += chips_increase;
         let message = format!(
-            "{} +100 Chips ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Chips ({} x {})",
+            joker_card.joker, chips_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -301,10 +311,11 @@ impl JokerEffect for CraftyJoker {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.chips += 80.0;
+        let chips_increase = game_state.balance.joker_value("Crafty Joker", 80.0);
+        game_state.chips += chips_increase;
         let message = format!(
-            "{} +80 Chips ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} +{} Chips ({} x {})",
+            joker_card.joker, chips_increase, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -326,7 +337,8 @@ impl JokerEffect for AbstractJoker {
         _current_card: &Card,
     ) -> GameResult<()> {
         let joker_count = game_state.round.jokers.len();
-        let mult_increase = 3.0 * (joker_count as f64);
+        let mult_increase =
+            game_state.balance.joker_value("Abstract Joker", 3.0) * (joker_count as f64);
         game_state.mult += mult_increase;
         let message = format!(
             "{} +{} Mult ({} x {})",
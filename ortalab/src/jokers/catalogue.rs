@@ -0,0 +1,235 @@
+//! # Joker Catalogue
+//!
+//! This module lists every joker ortalab knows about, using the metadata table
+//! next to `create_joker_effect`, together with the round files that cover it.
+//!
+//! A joker is covered by a fixture when it appears in the `jokers` section of
+//! a round file under one of the scanned directories. Files that are not round
+//! files (such as deck compositions or run descriptions) are skipped. Jokers
+//! added through the registry are listed after the built-in ones.
+//!
+//! A Balatro joker is unimplemented when neither `create_joker_effect` nor the
+//! registry has a joker of that name. Names are compared ignoring case,
+//! spaces and punctuation, so `Sock And Buskin` matches `Sock and Buskin`.
+//!
+//! ## Key Components
+//! - `build_catalogue()`: Collects the metadata and fixture coverage of every joker
+//! - `Catalogue`: The table printed by `ortalab jokers`, optionally with the
+//!   fixtures of every joker under its row
+//! - `BALATRO_JOKERS`: Every joker of the base game, to find the unimplemented ones
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use enum_iterator::all;
+use ortalib::Joker;
+
+use super::registry::custom_jokers;
+use super::{ActivationType, JokerInfo, create_joker_effect, joker_info};
+use crate::input::{InputFormat, parse_input};
+use crate::round::RoundInput;
+
+/// Every joker of Balatro, in collection order
+#[rustfmt::skip]
+pub const BALATRO_JOKERS: [&str; 150] = [
+    "Joker", "Greedy Joker", "Lusty Joker", "Wrathful Joker", "Gluttonous Joker",
+    "Jolly Joker", "Zany Joker", "Mad Joker", "Crazy Joker", "Droll Joker",
+    "Sly Joker", "Wily Joker", "Clever Joker", "Devious Joker", "Crafty Joker",
+    "Half Joker", "Joker Stencil", "Four Fingers", "Mime", "Credit Card",
+    "Ceremonial Dagger", "Banner", "Mystic Summit", "Marble Joker", "Loyalty Card",
+    "8 Ball", "Misprint", "Dusk", "Raised Fist", "Chaos the Clown",
+    "Fibonacci", "Steel Joker", "Scary Face", "Abstract Joker", "Delayed Gratification",
+    "Hack", "Pareidolia", "Gros Michel", "Even Steven", "Odd Todd",
+    "Scholar", "Business Card", "Supernova", "Ride the Bus", "Space Joker",
+    "Egg", "Burglar", "Blackboard", "Runner", "Ice Cream",
+    "DNA", "Splash", "Blue Joker", "Sixth Sense", "Constellation",
+    "Hiker", "Faceless Joker", "Green Joker", "Superposition", "To Do List",
+    "Cavendish", "Card Sharp", "Red Card", "Madness", "Square Joker",
+    "Seance", "Riff-raff", "Vampire", "Shortcut", "Hologram",
+    "Vagabond", "Baron", "Cloud 9", "Rocket", "Obelisk",
+    "Midas Mask", "Luchador", "Photograph", "Gift Card", "Turtle Bean",
+    "Erosion", "Reserved Parking", "Mail-In Rebate", "To the Moon", "Hallucination",
+    "Fortune Teller", "Juggler", "Drunkard", "Stone Joker", "Golden Joker",
+    "Lucky Cat", "Baseball Card", "Bull", "Diet Cola", "Trading Card",
+    "Flash Card", "Popcorn", "Spare Trousers", "Ancient Joker", "Ramen",
+    "Walkie Talkie", "Seltzer", "Castle", "Smiley Face", "Campfire",
+    "Golden Ticket", "Mr. Bones", "Acrobat", "Sock and Buskin", "Swashbuckler",
+    "Troubadour", "Certificate", "Smeared Joker", "Throwback", "Hanging Chad",
+    "Rough Gem", "Bloodstone", "Arrowhead", "Onyx Agate", "Glass Joker",
+    "Showman", "Flower Pot", "Blueprint", "Wee Joker", "Merry Andy",
+    "Oops! All 6s", "The Idol", "Seeing Double", "Matador", "Hit the Road",
+    "The Duo", "The Trio", "The Family", "The Order", "The Tribe",
+    "Stuntman", "Invisible Joker", "Brainstorm", "Satellite", "Shoot the Moon",
+    "Driver's License", "Cartomancer", "Astronomer", "Burnt Joker", "Bootstraps",
+    "Canio", "Triboulet", "Yorick", "Chicot", "Perkeo",
+];
+
+/// A joker name without case, spaces or punctuation, for comparing names
+fn normalised(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// One row of the catalogue
+#[derive(Debug, Clone)]
+pub struct CatalogueEntry {
+    /// The joker's name as written in round files
+    pub name: String,
+    pub activation: ActivationType,
+    pub info: JokerInfo,
+    /// Round files whose jokers include this joker
+    pub fixtures: Vec<PathBuf>,
+}
+
+/// Every joker with its metadata and fixture coverage
+#[derive(Debug, Clone)]
+pub struct Catalogue {
+    pub entries: Vec<CatalogueEntry>,
+    /// List the fixtures of every joker under its row
+    pub show_fixtures: bool,
+}
+
+impl Catalogue {
+    /// Balatro jokers that no catalogue entry implements
+    pub fn unimplemented(&self) -> impl Iterator<Item = &'static str> {
+        let implemented: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| normalised(&entry.name))
+            .collect();
+        BALATRO_JOKERS
+            .into_iter()
+            .filter(move |name| !implemented.contains(&normalised(name)))
+    }
+
+    /// Jokers that no fixture covers
+    pub fn uncovered(&self) -> impl Iterator<Item = &CatalogueEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.fixtures.is_empty())
+    }
+}
+
+impl fmt::Display for Catalogue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<18} {:<12} {:<9} {:>4} {:>8}  Description",
+            "Joker", "Activation", "Rarity", "Cost", "Fixtures"
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:<18} {:<12} {:<9} {:>4} {:>8}  {}",
+                entry.name,
+                format!("{:?}", entry.activation),
+                entry.info.rarity,
+                format!("${}", entry.info.cost),
+                entry.fixtures.len(),
+                entry.info.description
+            )?;
+            if self.show_fixtures {
+                for fixture in &entry.fixtures {
+                    writeln!(f, "    {}", fixture.display())?;
+                }
+            }
+        }
+
+        let unimplemented: Vec<&str> = self.unimplemented().collect();
+        writeln!(
+            f,
+            "\n{} of {} Balatro jokers implemented",
+            BALATRO_JOKERS.len() - unimplemented.len(),
+            BALATRO_JOKERS.len()
+        )?;
+
+        let names = |names: Vec<&str>| -> String {
+            if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            }
+        };
+        writeln!(f, "Unimplemented jokers: {}", names(unimplemented))?;
+        writeln!(
+            f,
+            "Jokers without fixtures: {}",
+            names(self.uncovered().map(|entry| entry.name.as_str()).collect())
+        )
+    }
+}
+
+/// Collects every file in an input format under `dir`, in a stable order
+fn round_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            round_files(&path, files);
+        } else if InputFormat::from_extension(&path).is_some() {
+            files.push(path);
+        }
+    }
+}
+
+/// Builds the catalogue, counting the round files under `fixture_dirs` that
+/// use each joker
+pub fn build_catalogue(fixture_dirs: &[PathBuf]) -> Catalogue {
+    let mut files = Vec::new();
+    for dir in fixture_dirs {
+        round_files(dir, &mut files);
+    }
+
+    let mut coverage: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for file in files {
+        let Ok(contents) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let Ok(input) = parse_input::<RoundInput>(&file, &contents) else {
+            continue;
+        };
+        let mut jokers: Vec<String> = input
+            .round
+            .jokers
+            .iter()
+            .map(|joker_card| match input.custom_jokers.get(joker_card) {
+                Some(custom) => custom.name.to_string(),
+                None => format!("{:?}", joker_card.joker),
+            })
+            .collect();
+        jokers.sort();
+        jokers.dedup();
+        for joker in jokers {
+            coverage.entry(joker).or_default().push(file.clone());
+        }
+    }
+
+    let mut entries: Vec<CatalogueEntry> = all::<Joker>()
+        .map(|joker| {
+            let name = format!("{:?}", joker);
+            CatalogueEntry {
+                activation: create_joker_effect(joker).activation_type(),
+                info: joker_info(joker),
+                fixtures: coverage.remove(&name).unwrap_or_default(),
+                name,
+            }
+        })
+        .collect();
+    entries.extend(custom_jokers().into_iter().map(|custom| CatalogueEntry {
+        name: custom.name.to_string(),
+        activation: custom.effect.activation_type(),
+        info: custom.info,
+        fixtures: coverage.remove(&*custom.name).unwrap_or_default(),
+    }));
+    Catalogue {
+        entries,
+        show_fixtures: false,
+    }
+}
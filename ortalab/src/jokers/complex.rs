@@ -1,9 +1,8 @@
 // src/jokers/complex.rs
 use crate::errors::GameResult;
-use crate::game::GameState;
+use crate::game::{GameState, RetriggerPhase, RetriggerSource};
 use crate::jokers::ActivationType;
 use crate::jokers::JokerEffect;
-use crate::jokers::create_joker_effect;
 use ortalib::Card;
 use ortalib::Joker;
 use ortalib::JokerCard;
@@ -82,9 +81,9 @@ impl JokerEffect for Mime {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        // Mark for retrigger rather than directly applying effects
+        // Request a retrigger rather than directly applying effects
         // The actual retrigger will happen in the game scoring logic
-        game_state.mime_retriggers += 1;
+        game_state.request_retrigger(RetriggerSource::Joker(*joker_card), RetriggerPhase::Held, 1);
 
         explain_dbg!(
             game_state,
@@ -163,11 +162,17 @@ impl JokerEffect for SockAndBuskin {
         &self,
         game_state: &mut GameState,
         joker_card: &JokerCard,
-        _current_card: &Card,
+        current_card: &Card,
     ) -> GameResult<()> {
-        // Mark for retrigger rather than directly applying effects
+        // Request a retrigger rather than directly applying effects
         // The actual retrigger will happen in the game scoring logic
-        game_state.sock_and_buskin_retriggers += 1;
+        if game_state.pareidolia_active || current_card.rank.is_face() {
+            game_state.request_retrigger(
+                RetriggerSource::Joker(*joker_card),
+                RetriggerPhase::Scored,
+                1,
+            );
+        }
 
         explain_dbg!(
             game_state,
@@ -244,65 +249,66 @@ impl JokerEffect for Blueprint {
         _current_card: &Card,
     ) -> GameResult<()> {
         // Find this blueprint's position in the jokers list
-        if let Some(joker_index) = game_state.round.jokers.iter().position(|j| j == joker_card) {
-            if let Some((_target_index, target_joker)) =
+        if let Some(joker_index) = game_state.round.jokers.iter().position(|j| j == joker_card)
+            && let Some((target_index, target_joker)) =
                 follow_blueprint_chain(game_state, joker_index)
-            {
-                // Get the target joker's effect
-                let effect = create_joker_effect(target_joker);
-
-                // Create a placeholder card for potential use
-                let placeholder_card = Card::new(Rank::Ace, Suit::Diamonds, None, None);
-
-                // Handle different activation types
-                match effect.activation_type() {
-                    ActivationType::Independent => {
-                        // Skip incompatible passive jokers
-                        if target_joker != Joker::FourFingers
-                            && target_joker != Joker::Shortcut
-                            && target_joker != Joker::Pareidolia
-                            && target_joker != Joker::Splash
-                            && target_joker != Joker::SmearedJoker
-                            && effect.can_apply(game_state)
-                        {
-                            effect.apply(game_state, joker_card, &placeholder_card)?;
-
-                            explain_dbg!(
-                                game_state,
-                                "{} copies ability of {}",
-                                joker_card.joker,
-                                target_joker
-                            );
-                            return Ok(());
-                        }
-                    }
-                    ActivationType::OnScored => {
-                        game_state
-                            .blueprint_copied_jokers
-                            .push((*joker_card, target_joker));
-
-                        explain_dbg!(
-                            game_state,
-                            "{} will copy OnScored ability of {}",
-                            joker_card.joker,
-                            target_joker
-                        );
-                        return Ok(());
-                    }
-                    ActivationType::OnHeld => {
-                        game_state
-                            .blueprint_held_jokers
-                            .push((*joker_card, target_joker));
+        {
+            // Get the target joker's effect (which may be a custom joker)
+            let target_card = game_state.round.jokers[target_index];
+            let effect = game_state.joker_effect(&target_card);
+            let target_name = game_state.joker_name(&target_card);
+
+            // Create a placeholder card for potential use
+            let placeholder_card = Card::new(Rank::Ace, Suit::Diamonds, None, None);
+
+            // Handle different activation types
+            match effect.activation_type() {
+                ActivationType::Independent => {
+                    // Skip incompatible passive jokers
+                    if target_joker != Joker::FourFingers
+                        && target_joker != Joker::Shortcut
+                        && target_joker != Joker::Pareidolia
+                        && target_joker != Joker::Splash
+                        && target_joker != Joker::SmearedJoker
+                        && effect.can_apply(game_state)
+                    {
+                        effect.apply(game_state, joker_card, &placeholder_card)?;
 
                         explain_dbg!(
                             game_state,
-                            "{} will copy OnHeld ability of {}",
+                            "{} copies ability of {}",
                             joker_card.joker,
-                            target_joker
+                            target_name
                         );
                         return Ok(());
                     }
                 }
+                ActivationType::OnScored => {
+                    game_state
+                        .blueprint_copied_jokers
+                        .push((*joker_card, target_card));
+
+                    explain_dbg!(
+                        game_state,
+                        "{} will copy OnScored ability of {}",
+                        joker_card.joker,
+                        target_name
+                    );
+                    return Ok(());
+                }
+                ActivationType::OnHeld => {
+                    game_state
+                        .blueprint_held_jokers
+                        .push((*joker_card, target_card));
+
+                    explain_dbg!(
+                        game_state,
+                        "{} will copy OnHeld ability of {}",
+                        joker_card.joker,
+                        target_name
+                    );
+                    return Ok(());
+                }
             }
         }
 
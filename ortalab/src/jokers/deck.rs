@@ -0,0 +1,239 @@
+//! # Deck Jokers
+//!
+//! Jokers that depend on the cards in the deck, read from `GameState::deck` (the
+//! full deck) and `GameState::remaining_deck`. Like the history jokers, ortalib
+//! has no `Joker` variants for them, so ortalab registers them in the joker registry.
+//!
+//! A round without the deck they need gives them no effect.
+
+use ortalib::{Card, Enhancement, JokerCard};
+
+use super::registry::{CustomJoker, bundled_joker};
+use super::{ActivationType, JokerEffect, Rarity};
+use crate::errors::GameResult;
+use crate::explain_dbg;
+use crate::game::GameState;
+
+/// Number of cards every deck starts with
+const STARTING_DECK_SIZE: usize = 52;
+
+/// Enhanced cards needed in the full deck for Driver's License
+const DRIVERS_LICENSE_ENHANCED: usize = 16;
+
+/// The jokers of this module, ready to be registered
+#[rustfmt::skip]
+pub fn deck_jokers() -> Vec<CustomJoker> {
+    vec![
+        bundled_joker("Blue Joker",       Rarity::Common,   5, "+2 Chips for each remaining card in the deck", BlueJoker),
+        bundled_joker("Erosion",          Rarity::Uncommon, 6, "+4 Mult for each card below the deck's starting size in the full deck", Erosion),
+        bundled_joker("Stone Joker",      Rarity::Uncommon, 6, "+25 Chips for each Stone card in the full deck", StoneJoker),
+        bundled_joker("Steel Joker",      Rarity::Uncommon, 7, "x0.2 Mult for each Steel card in the full deck", SteelJoker),
+        bundled_joker("Driver's License", Rarity::Rare,     7, "x3 Mult if the full deck has at least 16 enhanced cards", DriversLicense),
+    ]
+}
+
+/// The cards a joker reads, explaining when the round did not give them
+fn require<'a>(
+    game_state: &GameState,
+    joker_card: &JokerCard,
+    cards: &'a Option<Vec<Card>>,
+) -> Option<&'a [Card]> {
+    if cards.is_none() {
+        explain_dbg!(
+            game_state,
+            "{} has no effect (the round has no deck)",
+            game_state.joker_name(joker_card)
+        );
+    }
+    cards.as_deref()
+}
+
+// +2 Chips for each card remaining in the deck
+pub struct BlueJoker;
+
+impl JokerEffect for BlueJoker {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let remaining_deck = game_state.remaining_deck.clone();
+        let Some(remaining) = require(game_state, joker_card, &remaining_deck) else {
+            return Ok(());
+        };
+        let chips_increase =
+            game_state.balance.joker_value("Blue Joker", 2.0) * remaining.len() as f64;
+        game_state.chips += chips_increase;
+
+        let message = format!(
+            "{} +{} Chips ({} x {})",
+            game_state.joker_name(joker_card),
+            chips_increase,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
+
+// +4 Mult for each card the full deck is short of its starting size
+pub struct Erosion;
+
+impl JokerEffect for Erosion {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let deck = game_state.deck.clone();
+        let Some(deck) = require(game_state, joker_card, &deck) else {
+            return Ok(());
+        };
+        let missing = STARTING_DECK_SIZE.saturating_sub(deck.len());
+        if missing == 0 {
+            return Ok(());
+        }
+        let mult_increase = game_state.balance.joker_value("Erosion", 4.0) * missing as f64;
+        game_state.mult += mult_increase;
+
+        let message = format!(
+            "{} +{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_increase,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
+
+// +25 Chips for each Stone card in the full deck
+pub struct StoneJoker;
+
+impl JokerEffect for StoneJoker {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let deck = game_state.deck.clone();
+        let Some(deck) = require(game_state, joker_card, &deck) else {
+            return Ok(());
+        };
+        let stones = deck.iter().filter(|card| game_state.is_stone(card)).count();
+        if stones == 0 {
+            return Ok(());
+        }
+        let chips_increase = game_state.balance.joker_value("Stone Joker", 25.0) * stones as f64;
+        game_state.chips += chips_increase;
+
+        let message = format!(
+            "{} +{} Chips ({} x {})",
+            game_state.joker_name(joker_card),
+            chips_increase,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
+
+// x0.2 Mult for each Steel card in the full deck
+pub struct SteelJoker;
+
+impl JokerEffect for SteelJoker {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let deck = game_state.deck.clone();
+        let Some(deck) = require(game_state, joker_card, &deck) else {
+            return Ok(());
+        };
+        let steels = deck
+            .iter()
+            .filter(|card| card.enhancement == Some(Enhancement::Steel))
+            .count();
+        if steels == 0 {
+            return Ok(());
+        }
+        let mult_factor = 1.0 + game_state.balance.joker_value("Steel Joker", 0.2) * steels as f64;
+        game_state.mult *= mult_factor;
+
+        let message = format!(
+            "{} x{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_factor,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
+
+// x3 Mult if the full deck has at least 16 enhanced cards
+pub struct DriversLicense;
+
+impl JokerEffect for DriversLicense {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let deck = game_state.deck.clone();
+        let Some(deck) = require(game_state, joker_card, &deck) else {
+            return Ok(());
+        };
+        let enhanced = deck
+            .iter()
+            .filter(|card| {
+                card.enhancement.is_some() || game_state.extras(card).enhancement.is_some()
+            })
+            .count();
+        if enhanced < DRIVERS_LICENSE_ENHANCED {
+            return Ok(());
+        }
+        let mult_factor = game_state.balance.joker_value("Driver's License", 3.0);
+        game_state.mult *= mult_factor;
+
+        let message = format!(
+            "{} x{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_factor,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
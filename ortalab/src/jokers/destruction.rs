@@ -0,0 +1,96 @@
+//! # Destruction Jokers
+//!
+//! Jokers that react to cards being destroyed, told about each one through
+//! `JokerEffect::on_card_destroyed`. ortalib has no `Joker` variants for them,
+//! so ortalab registers them in the joker registry.
+//!
+//! Cards destroyed by consumables are gone before scoring starts, so these
+//! jokers count them for the current hand. Glass cards shatter after scoring,
+//! so the gain is reported but only counts towards later hands.
+
+use ortalib::{Card, Enhancement, JokerCard};
+
+use super::registry::{CustomJoker, bundled_joker};
+use super::{ActivationType, JokerEffect, Rarity};
+use crate::errors::GameResult;
+use crate::explain_dbg;
+use crate::game::{DestroyedCard, GameState};
+
+/// The jokers of this module, ready to be registered
+#[rustfmt::skip]
+pub fn destruction_jokers() -> Vec<CustomJoker> {
+    vec![
+        bundled_joker("Glass Joker", Rarity::Uncommon, 6, "x0.75 Mult for every Glass card destroyed", GlassJoker),
+    ]
+}
+
+/// Glass cards destroyed so far while playing the hand
+fn glass_cards_destroyed(game_state: &GameState) -> usize {
+    game_state
+        .destroyed_cards
+        .iter()
+        .filter(|destroyed| destroyed.card.enhancement == Some(Enhancement::Glass))
+        .count()
+}
+
+// x0.75 Mult for every Glass card destroyed
+pub struct GlassJoker;
+
+impl GlassJoker {
+    /// The joker's x Mult after `destroyed` Glass cards
+    fn mult_factor(game_state: &GameState, destroyed: usize) -> f64 {
+        1.0 + game_state.balance.joker_value("Glass Joker", 0.75) * destroyed as f64
+    }
+}
+
+impl JokerEffect for GlassJoker {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let destroyed = glass_cards_destroyed(game_state);
+        if destroyed == 0 {
+            return Ok(());
+        }
+        let mult_factor = Self::mult_factor(game_state, destroyed);
+        game_state.mult *= mult_factor;
+
+        let message = format!(
+            "{} x{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_factor,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+
+    fn on_card_destroyed(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        destroyed: &DestroyedCard,
+    ) -> GameResult<()> {
+        if destroyed.card.enhancement != Some(Enhancement::Glass) {
+            return Ok(());
+        }
+        // The destroyed card is already recorded, so it is part of the count
+        let mult_factor = Self::mult_factor(game_state, glass_cards_destroyed(game_state));
+
+        let message = format!(
+            "{} gains x{} Mult (now x{})",
+            game_state.joker_name(joker_card),
+            game_state.balance.joker_value("Glass Joker", 0.75),
+            mult_factor
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
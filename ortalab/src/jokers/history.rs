@@ -0,0 +1,178 @@
+//! # History Jokers
+//!
+//! Jokers that depend on earlier hands of the run, read from `GameState::history`.
+//! ortalib has no `Joker` variants for them, so ortalab registers them in the
+//! joker registry, and round files use them by name like any other joker.
+
+use ortalib::{Card, JokerCard};
+
+use super::registry::{CustomJoker, bundled_joker};
+use super::{ActivationType, JokerEffect, Rarity};
+use crate::errors::GameResult;
+use crate::explain_dbg;
+use crate::game::GameState;
+
+/// The jokers of this module, ready to be registered
+#[rustfmt::skip]
+pub fn history_jokers() -> Vec<CustomJoker> {
+    vec![
+        bundled_joker("Supernova",    Rarity::Common,   5, "Adds the number of times the played poker hand has been played this run to Mult", Supernova),
+        bundled_joker("Card Sharp",   Rarity::Uncommon, 6, "x3 Mult if the played poker hand has already been played this round", CardSharp),
+        bundled_joker("Obelisk",      Rarity::Rare,     8, "x0.2 Mult per consecutive hand played without playing the most played poker hand", Obelisk),
+        bundled_joker("Ride the Bus", Rarity::Common,   6, "+1 Mult per consecutive hand played without a scoring face card", RideTheBus),
+    ]
+}
+
+// +Mult for every time the poker hand has been played this run, this hand included
+pub struct Supernova;
+
+impl JokerEffect for Supernova {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let Some(hand) = game_state.poker_hand else {
+            return Ok(());
+        };
+        let mult_increase = game_state.balance.joker_value("Supernova", 1.0)
+            * f64::from(game_state.history.times_played(hand) + 1);
+        game_state.mult += mult_increase;
+
+        let message = format!(
+            "{} +{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_increase,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
+
+// x3 Mult if the poker hand was already played this round
+pub struct CardSharp;
+
+impl JokerEffect for CardSharp {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let Some(hand) = game_state.poker_hand else {
+            return Ok(());
+        };
+        if game_state.history.played_this_round(hand) {
+            let mult_factor = game_state.balance.joker_value("Card Sharp", 3.0);
+            game_state.mult *= mult_factor;
+
+            let message = format!(
+                "{} x{} Mult ({} x {})",
+                game_state.joker_name(joker_card),
+                mult_factor,
+                game_state.chips,
+                game_state.mult
+            );
+            explain_dbg!(game_state, "{}", message);
+        }
+        Ok(())
+    }
+}
+
+// x0.2 Mult per hand in a row without the most played poker hand, this hand included
+pub struct Obelisk;
+
+impl JokerEffect for Obelisk {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        let Some(hand) = game_state.poker_hand else {
+            return Ok(());
+        };
+        // Playing the most played hand resets the streak
+        if game_state.history.is_most_played(hand) {
+            explain_dbg!(
+                game_state,
+                "{} resets ({} is the most played hand)",
+                game_state.joker_name(joker_card),
+                hand
+            );
+            return Ok(());
+        }
+
+        let streak = game_state.history.hands_without_most_played + 1;
+        let mult_factor = 1.0 + game_state.balance.joker_value("Obelisk", 0.2) * f64::from(streak);
+        game_state.mult *= mult_factor;
+
+        let message = format!(
+            "{} x{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_factor,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
+
+// +1 Mult per hand in a row without a scoring face card, this hand included
+pub struct RideTheBus;
+
+impl JokerEffect for RideTheBus {
+    fn activation_type(&self) -> ActivationType {
+        ActivationType::Independent
+    }
+
+    fn apply(
+        &self,
+        game_state: &mut GameState,
+        joker_card: &JokerCard,
+        _current_card: &Card,
+    ) -> GameResult<()> {
+        // Stone cards have no rank, so they are never face cards
+        let scores_face_card = game_state.scoring_cards.iter().any(|card| {
+            !game_state.is_stone(card) && (game_state.pareidolia_active || card.rank.is_face())
+        });
+        if scores_face_card {
+            explain_dbg!(
+                game_state,
+                "{} resets (a face card scored)",
+                game_state.joker_name(joker_card)
+            );
+            return Ok(());
+        }
+
+        let mult_increase = game_state.balance.joker_value("Ride the Bus", 1.0)
+            * f64::from(game_state.history.consecutive_non_face_hands + 1);
+        game_state.mult += mult_increase;
+
+        let message = format!(
+            "{} +{} Mult ({} x {})",
+            game_state.joker_name(joker_card),
+            mult_increase,
+            game_state.chips,
+            game_state.mult
+        );
+        explain_dbg!(game_state, "{}", message);
+        Ok(())
+    }
+}
@@ -3,8 +3,9 @@ use crate::errors::GameResult;
 use crate::game::GameState;
 use crate::jokers::ActivationType;
 use crate::jokers::JokerEffect;
+use crate::poker::{SuitAssignment, cover_every_suit};
 use ortalib::Card;
-use ortalib::{Enhancement, JokerCard, Rank, Suit};
+use ortalib::{JokerCard, Rank, Suit};
 
 use crate::explain_dbg;
 
@@ -23,12 +24,16 @@ impl JokerEffect for RaisedFist {
         current_card: &Card,
     ) -> GameResult<()> {
         let rank_value = current_card.rank.rank_value();
-        let mult_increase = 2.0 * rank_value;
+        let mult_increase = game_state.balance.joker_value("Raised Fist", 2.0) * rank_value;
         game_state.mult += mult_increase;
 
         let message = format!(
             "{} {} +{} Mult ({} x {})",
-            joker_card.joker, current_card, mult_increase, game_state.chips, game_state.mult
+            joker_card.joker,
+            game_state.label(current_card),
+            mult_increase,
+            game_state.chips,
+            game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -49,11 +54,10 @@ impl JokerEffect for Blackboard {
             return true;
         }
 
-        // Check if all cards are spades or clubs (or wild)
+        // Check if all cards count as spades or clubs
         game_state.round.cards_held_in_hand.iter().all(|card| {
-            card.suit == Suit::Spades
-                || card.suit == Suit::Clubs
-                || card.enhancement == Some(Enhancement::Wild)
+            game_state.counts_as_suit(card, Suit::Spades)
+                || game_state.counts_as_suit(card, Suit::Clubs)
         })
     }
 
@@ -63,10 +67,11 @@ impl JokerEffect for Blackboard {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult *= 3.0;
+        let mult_factor = game_state.balance.joker_value("Blackboard", 3.0);
+        game_state.mult *= mult_factor;
         let message = format!(
-            "{} x3 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} x{} Mult ({} x {})",
+            joker_card.joker, mult_factor, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
@@ -88,10 +93,15 @@ impl JokerEffect for Baron {
         current_card: &Card,
     ) -> GameResult<()> {
         if current_card.rank == Rank::King {
-            game_state.mult *= 1.5;
+            let mult_factor = game_state.balance.joker_value("Baron", 1.5);
+            game_state.mult *= mult_factor;
             let message = format!(
-                "{} {} x1.5 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} x{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_factor,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -100,6 +110,32 @@ impl JokerEffect for Baron {
     }
 }
 
+/// Whether a card counts as a suit joker's suit, reporting a card counted as
+/// the suit by being Wild or by Smeared Joker (e.g. `K♥ counted as ♦ for Greedy Joker`)
+///
+/// This ignores the hand's `SuitAssignment`s on purpose: as in Balatro, a Wild
+/// card counts for every suit joker, not only for the suit the Flush chose.
+fn counts_for_suit_joker(
+    game_state: &GameState,
+    joker_card: &JokerCard,
+    card: &Card,
+    suit: Suit,
+) -> bool {
+    if !game_state.counts_as_suit(card, suit) {
+        return false;
+    }
+    if card.suit != suit {
+        explain_dbg!(
+            game_state,
+            "{} counted as {} for {}",
+            game_state.label(card),
+            suit,
+            joker_card.joker
+        );
+    }
+    true
+}
+
 // ✖ Mult +3 for each ♦Diamonds card played
 pub struct GreedyJoker;
 
@@ -114,13 +150,16 @@ impl JokerEffect for GreedyJoker {
         joker_card: &JokerCard,
         current_card: &Card,
     ) -> GameResult<()> {
-        if current_card.suit == Suit::Diamonds
-            || current_card.enhancement == Some(Enhancement::Wild)
-        {
-            game_state.mult += 3.0;
+        if counts_for_suit_joker(game_state, joker_card, current_card, Suit::Diamonds) {
+            let mult_increase = game_state.balance.joker_value("Greedy Joker", 3.0);
+            game_state.mult += mult_increase;
             let message = format!(
-                "{} {} +3 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -143,12 +182,16 @@ impl JokerEffect for LustyJoker {
         joker_card: &JokerCard,
         current_card: &Card,
     ) -> GameResult<()> {
-        if current_card.suit == Suit::Hearts || current_card.enhancement == Some(Enhancement::Wild)
-        {
-            game_state.mult += 3.0;
+        if counts_for_suit_joker(game_state, joker_card, current_card, Suit::Hearts) {
+            let mult_increase = game_state.balance.joker_value("Lusty Joker", 3.0);
+            game_state.mult += mult_increase;
             let message = format!(
-                "{} {} +3 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -171,12 +214,16 @@ impl JokerEffect for WrathfulJoker {
         joker_card: &JokerCard,
         current_card: &Card,
     ) -> GameResult<()> {
-        if current_card.suit == Suit::Spades || current_card.enhancement == Some(Enhancement::Wild)
-        {
-            game_state.mult += 3.0;
+        if counts_for_suit_joker(game_state, joker_card, current_card, Suit::Spades) {
+            let mult_increase = game_state.balance.joker_value("Wrathful Joker", 3.0);
+            game_state.mult += mult_increase;
             let message = format!(
-                "{} {} +3 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -199,11 +246,16 @@ impl JokerEffect for GluttonousJoker {
         joker_card: &JokerCard,
         current_card: &Card,
     ) -> GameResult<()> {
-        if current_card.suit == Suit::Clubs || current_card.enhancement == Some(Enhancement::Wild) {
-            game_state.mult += 3.0;
+        if counts_for_suit_joker(game_state, joker_card, current_card, Suit::Clubs) {
+            let mult_increase = game_state.balance.joker_value("Gluttonous Joker", 3.0);
+            game_state.mult += mult_increase;
             let message = format!(
-                "{} {} +3 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -234,10 +286,15 @@ impl JokerEffect for Fibonacci {
                 || card.rank == Rank::Five
                 || card.rank == Rank::Eight
             {
-                game_state.mult += 8.0;
+                let mult_increase = game_state.balance.joker_value("Fibonacci", 8.0);
+                game_state.mult += mult_increase;
                 let message = format!(
-                    "{} {} +8 Mult ({} x {})",
-                    joker_card.joker, card, game_state.chips, game_state.mult
+                    "{} {} +{} Mult ({} x {})",
+                    joker_card.joker,
+                    game_state.label(card),
+                    mult_increase,
+                    game_state.chips,
+                    game_state.mult
                 );
                 explain_dbg!(game_state, "{}", message);
             }
@@ -264,10 +321,15 @@ impl JokerEffect for ScaryFace {
         let is_face = game_state.pareidolia_active || current_card.rank.is_face();
 
         if is_face {
-            game_state.chips += 30.0;
+            let chips_increase = game_state.balance.joker_value("Scary Face", 30.0);
+            game_state.chips += chips_increase;
             let message = format!(
-                "{} {} +30 Chips ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Chips ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                chips_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -296,10 +358,15 @@ impl JokerEffect for EvenSteven {
         );
 
         if is_even_rank {
-            game_state.mult += 4.0;
+            let mult_increase = game_state.balance.joker_value("Even Steven", 4.0);
+            game_state.mult += mult_increase;
             let message = format!(
-                "{} {} +4 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -328,10 +395,15 @@ impl JokerEffect for OddTodd {
         );
 
         if is_odd_rank {
-            game_state.chips += 31.0;
+            let chips_increase = game_state.balance.joker_value("Odd Todd", 31.0);
+            game_state.chips += chips_increase;
             let message = format!(
-                "{} {} +31 Chips ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Chips ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                chips_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -357,11 +429,16 @@ impl JokerEffect for Photograph {
         let is_face = game_state.pareidolia_active || current_card.rank.is_face();
 
         if is_face && !game_state.first_face_card_processed {
-            game_state.mult *= 2.0;
+            let mult_factor = game_state.balance.joker_value("Photograph", 2.0);
+            game_state.mult *= mult_factor;
             game_state.first_face_card_processed = true;
             let message = format!(
-                "{} {} x2 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} x{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_factor,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -386,10 +463,15 @@ impl JokerEffect for SmileyFace {
         let is_face = game_state.pareidolia_active || current_card.rank.is_face();
 
         if is_face {
-            game_state.mult += 5.0;
+            let mult_increase = game_state.balance.joker_value("Smiley Face", 5.0);
+            game_state.mult += mult_increase;
             let message = format!(
-                "{} {} +5 Mult ({} x {})",
-                joker_card.joker, current_card, game_state.chips, game_state.mult
+                "{} {} +{} Mult ({} x {})",
+                joker_card.joker,
+                game_state.label(current_card),
+                mult_increase,
+                game_state.chips,
+                game_state.mult
             );
             explain_dbg!(game_state, "{}", message);
         }
@@ -406,49 +488,7 @@ impl JokerEffect for FlowerPot {
     }
 
     fn can_apply(&self, game_state: &GameState) -> bool {
-        // Need at least 4 scoring cards
-        if game_state.scoring_cards.len() < 4 {
-            return false;
-        }
-
-        let smeared_active = game_state.smeared_joker_active;
-
-        // Collect natural suits (from non-wild cards) and count wilds
-        use std::collections::HashSet;
-        let mut natural_suits = HashSet::new();
-        let mut wild_count = 0;
-        let mut red_count = 0;
-        let mut black_count = 0;
-
-        for card in &game_state.scoring_cards {
-            if card.enhancement == Some(Enhancement::Wild) {
-                wild_count += 1;
-            } else {
-                natural_suits.insert(card.suit);
-                // Count red suits (♦, ♥)
-                if card.suit == Suit::Diamonds || card.suit == Suit::Hearts {
-                    red_count += 1;
-                }
-                // Count black suits (♣, ♠)
-                if card.suit == Suit::Clubs || card.suit == Suit::Spades {
-                    black_count += 1;
-                }
-            }
-        }
-        if smeared_active {
-            let missing_colors = (if red_count <= 2 { 2 - red_count } else { 0 })
-                + (if black_count <= 2 { 2 - black_count } else { 0 });
-            wild_count >= missing_colors
-        } else {
-            // Check if we have all four suits (natural or covered by wilds)
-            let required_suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
-            let present_suits = required_suits
-                .iter()
-                .filter(|&s| natural_suits.contains(s))
-                .count();
-            let missing_suits = 4 - present_suits;
-            wild_count >= missing_suits
-        }
+        flower_pot_assignments(game_state).is_some()
     }
     fn apply(
         &self,
@@ -456,12 +496,34 @@ impl JokerEffect for FlowerPot {
         joker_card: &JokerCard,
         _current_card: &Card,
     ) -> GameResult<()> {
-        game_state.mult *= 3.0;
+        for assignment in flower_pot_assignments(game_state).unwrap_or_default() {
+            explain_dbg!(
+                game_state,
+                "{} counted as {} for {}",
+                game_state.label(&assignment.card),
+                assignment.suit,
+                joker_card.joker
+            );
+        }
+        let mult_factor = game_state.balance.joker_value("Flower Pot", 3.0);
+        game_state.mult *= mult_factor;
         let message = format!(
-            "{} x3 Mult ({} x {})",
-            joker_card.joker, game_state.chips, game_state.mult
+            "{} x{} Mult ({} x {})",
+            joker_card.joker, mult_factor, game_state.chips, game_state.mult
         );
         explain_dbg!(game_state, "{}", message);
         Ok(())
     }
 }
+
+/// How the scoring cards cover all four suits for Flower Pot, if they do
+fn flower_pot_assignments(game_state: &GameState) -> Option<Vec<SuitAssignment>> {
+    // Stone cards have no suit
+    let suited: Vec<Card> = game_state
+        .scoring_cards
+        .iter()
+        .filter(|card| !game_state.is_stone(card))
+        .copied()
+        .collect();
+    cover_every_suit(&suited, game_state.smeared_joker_active)
+}
@@ -6,20 +6,37 @@
 //! - `basic`: Contains implementations for simple jokers with straightforward effects
 //! - `medium`: Contains implementations for intermediate complexity jokers
 //! - `complex`: Contains implementations for advanced jokers with complex effects
+//! - `history`: Jokers that depend on earlier hands of the run, which ortalib does not have
+//! - `deck`: Jokers that depend on the deck, which ortalib does not have either
+//! - `destruction`: Jokers that react to cards being destroyed, also missing from ortalib
+//! - `catalogue`: Lists every joker with its metadata and fixture coverage
+//! - `registry`: Custom jokers registered by name from outside ortalab
 //!
 //! ## Core Components
 //! - `ActivationType`: Enum defining when joker effects activate
 //! - `JokerEffect`: Trait that all joker implementations must implement
 //! - `create_joker_effect`: Factory function to create the appropriate joker effect
-//! - `apply_joker_edition`: Handles special editions of jokers (Foil, Holographic, Polychrome)
+//!   (custom jokers are resolved through `GameState::joker_effect` instead)
+//! - `joker_info`: Metadata (rarity, cost, description) for each joker the factory creates
+//! - `apply_joker_edition`: Handles special editions of jokers (Foil, Holographic, Polychrome);
+//!   Negative has no scoring effect and only frees a joker slot, which `round.rs` checks
 //! - `process_jokers`: Orchestrates the application of joker effects in the correct order
 pub mod basic;
+pub mod catalogue;
 pub mod complex;
+pub mod deck;
+pub mod destruction;
+pub mod history;
 pub mod medium;
+pub mod registry;
+
+use std::fmt;
 
 use ortalib::{Card, Chips, Edition, Joker, JokerCard, Mult, Rank, Suit};
 
-use crate::{errors::GameResult, game::GameState};
+use crate::balance::EditionValues;
+use crate::errors::GameResult;
+use crate::game::{DestroyedCard, GameState};
 
 use crate::explain_dbg_bool;
 
@@ -32,7 +49,10 @@ pub enum ActivationType {
 }
 
 /// Core trait for all joker effects
-pub trait JokerEffect {
+///
+/// Implement it outside ortalab and register it with `registry::register_joker`
+/// to add a custom joker.
+pub trait JokerEffect: Send + Sync {
     /// The type of activation for this joker
     fn activation_type(&self) -> ActivationType;
 
@@ -48,6 +68,18 @@ pub trait JokerEffect {
     fn can_apply(&self, _game_state: &GameState) -> bool {
         true // Default implementation
     }
+
+    /// Optional method for reacting to a card being destroyed
+    ///
+    /// Called once for every destroyed card, whatever the joker's activation type.
+    fn on_card_destroyed(
+        &self,
+        _game_state: &mut GameState,
+        _joker_card: &JokerCard,
+        _destroyed: &DestroyedCard,
+    ) -> GameResult<()> {
+        Ok(()) // Default implementation
+    }
 }
 
 /// Creates the appropriate joker effect based on joker type
@@ -95,40 +127,130 @@ pub fn create_joker_effect(joker: Joker) -> Box<dyn JokerEffect> {
     }
 }
 
+/// How rarely a joker appears in the shop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl fmt::Display for Rarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Rarity::Common => "Common",
+            Rarity::Uncommon => "Uncommon",
+            Rarity::Rare => "Rare",
+        };
+        f.pad(name)
+    }
+}
+
+/// Metadata about a joker, alongside the effect created by `create_joker_effect`
+#[derive(Debug, Clone, Copy)]
+pub struct JokerInfo {
+    pub rarity: Rarity,
+    /// Base shop price, before any edition
+    pub cost: u32,
+    pub description: &'static str,
+}
+
+/// Returns the metadata of a joker
+pub fn joker_info(joker: Joker) -> JokerInfo {
+    use Rarity::*;
+
+    #[rustfmt::skip]
+    let (rarity, cost, description) = match joker {
+        // Stage 3 - Basic jokers
+        Joker::Joker =>           (Common, 2, "+4 Mult"),
+        Joker::JollyJoker =>      (Common, 3, "+8 Mult if played hand contains a Pair"),
+        Joker::ZanyJoker =>       (Common, 4, "+12 Mult if played hand contains a Three of a Kind"),
+        Joker::MadJoker =>        (Common, 4, "+10 Mult if played hand contains a Two Pair"),
+        Joker::CrazyJoker =>      (Common, 4, "+12 Mult if played hand contains a Straight"),
+        Joker::DrollJoker =>      (Common, 4, "+10 Mult if played hand contains a Flush"),
+        Joker::SlyJoker =>        (Common, 3, "+50 Chips if played hand contains a Pair"),
+        Joker::WilyJoker =>       (Common, 4, "+100 Chips if played hand contains a Three of a Kind"),
+        Joker::CleverJoker =>     (Common, 4, "+80 Chips if played hand contains a Two Pair"),
+        Joker::DeviousJoker =>    (Common, 4, "+100 Chips if played hand contains a Straight"),
+        Joker::CraftyJoker =>     (Common, 4, "+80 Chips if played hand contains a Flush"),
+        Joker::AbstractJoker =>   (Common, 4, "+3 Mult for each Joker card"),
+
+        // Stage 4 - Medium jokers
+        Joker::RaisedFist =>      (Common, 5, "Adds double the rank of the lowest card held in hand to Mult"),
+        Joker::Blackboard =>      (Uncommon, 6, "x3 Mult if all cards held in hand are Spades or Clubs"),
+        Joker::Baron =>           (Rare, 8, "Each King held in hand gives x1.5 Mult"),
+        Joker::GreedyJoker =>     (Common, 5, "Played Diamond cards give +3 Mult when scored"),
+        Joker::LustyJoker =>      (Common, 5, "Played Heart cards give +3 Mult when scored"),
+        Joker::WrathfulJoker =>   (Common, 5, "Played Spade cards give +3 Mult when scored"),
+        Joker::GluttonousJoker => (Common, 5, "Played Club cards give +3 Mult when scored"),
+        Joker::Fibonacci =>       (Uncommon, 8, "Each played Ace, 2, 3, 5 or 8 gives +8 Mult when scored"),
+        Joker::ScaryFace =>       (Common, 4, "Played face cards give +30 Chips when scored"),
+        Joker::EvenSteven =>      (Common, 4, "Played cards with even rank give +4 Mult when scored"),
+        Joker::OddTodd =>         (Common, 4, "Played cards with odd rank give +31 Chips when scored"),
+        Joker::Photograph =>      (Common, 5, "First played face card gives x2 Mult when scored"),
+        Joker::SmileyFace =>      (Common, 4, "Played face cards give +5 Mult when scored"),
+        Joker::FlowerPot =>       (Uncommon, 6, "x3 Mult if scoring cards contain a Diamond, Club, Heart and Spade"),
+
+        // Stage 5 - Complex jokers
+        Joker::FourFingers =>     (Uncommon, 7, "All Flushes and Straights can be made with 4 cards"),
+        Joker::Shortcut =>        (Uncommon, 7, "Allows Straights to be made with gaps of 1 rank"),
+        Joker::Mime =>            (Uncommon, 5, "Retrigger all card held in hand abilities"),
+        Joker::Pareidolia =>      (Uncommon, 5, "All cards are considered face cards"),
+        Joker::Splash =>          (Common, 3, "Every played card counts in scoring"),
+        Joker::SockAndBuskin =>   (Uncommon, 6, "Retrigger all played face cards"),
+        Joker::SmearedJoker =>    (Uncommon, 7, "Hearts and Diamonds count as the same suit, as do Spades and Clubs"),
+        Joker::Blueprint =>       (Rare, 10, "Copies the ability of the Joker to the right"),
+    };
+
+    JokerInfo {
+        rarity,
+        cost,
+        description,
+    }
+}
+
 /// Processes joker editions (Foil, Holographic, Polychrome)
+///
+/// Negative has no scoring effect, so it is not handled here: it only frees a
+/// joker slot, which is checked in `round.rs`.
 pub fn apply_joker_edition(
     joker_card: &JokerCard,
+    name: &str,
+    values: &EditionValues,
     chips: &mut Chips,
     mult: &mut Mult,
     explain_enabled: bool,
 ) -> GameResult<()> {
     match joker_card.edition {
         Some(Edition::Foil) => {
-            *chips += 50.0;
+            *chips += values.foil;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} Foil +50 Chips ({} x {})",
-                joker_card.joker,
+                "{} Foil +{} Chips ({} x {})",
+                name,
+                values.foil,
                 chips,
                 mult
             );
         }
         Some(Edition::Holographic) => {
-            *mult += 10.0;
+            *mult += values.holographic;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} Holographic +10 Mult ({} x {})",
-                joker_card.joker,
+                "{} Holographic +{} Mult ({} x {})",
+                name,
+                values.holographic,
                 chips,
                 mult
             );
         }
         Some(Edition::Polychrome) => {
-            *mult *= 1.5;
+            *mult *= values.polychrome;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} Polychrome x1.5 Mult ({} x {})",
-                joker_card.joker,
+                "{} Polychrome x{} Mult ({} x {})",
+                name,
+                values.polychrome,
                 chips,
                 mult
             );
@@ -141,10 +263,13 @@ pub fn apply_joker_edition(
 /// Helper function to apply joker effects in the proper order
 pub fn process_jokers(game_state: &mut GameState) -> GameResult<()> {
     // Stage 1: Process joker editions (Foil, Holographic) before independent activation
-    for joker_card in &game_state.round.jokers {
+    for joker_card in &game_state.round.jokers.to_vec() {
         if let Some(Edition::Foil) | Some(Edition::Holographic) = joker_card.edition {
+            let name = game_state.joker_name(joker_card);
             apply_joker_edition(
                 joker_card,
+                &name,
+                &game_state.balance.editions,
                 &mut game_state.chips,
                 &mut game_state.mult,
                 game_state.explain_enabled,
@@ -153,7 +278,7 @@ pub fn process_jokers(game_state: &mut GameState) -> GameResult<()> {
     }
     // Stage 2: Process independent jokers
     for joker_card in &game_state.round.jokers.to_vec() {
-        let joker_effect = create_joker_effect(joker_card.joker);
+        let joker_effect = game_state.joker_effect(joker_card);
 
         if joker_effect.activation_type() == ActivationType::Independent
             && joker_effect.can_apply(game_state)
@@ -163,10 +288,13 @@ pub fn process_jokers(game_state: &mut GameState) -> GameResult<()> {
         }
     }
     // Stage 3: Process Polychrome editions after all jokers have been applied
-    for joker_card in &game_state.round.jokers {
+    for joker_card in &game_state.round.jokers.to_vec() {
         if let Some(Edition::Polychrome) = joker_card.edition {
+            let name = game_state.joker_name(joker_card);
             apply_joker_edition(
                 joker_card,
+                &name,
+                &game_state.balance.editions,
                 &mut game_state.chips,
                 &mut game_state.mult,
                 game_state.explain_enabled,
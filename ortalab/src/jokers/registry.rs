@@ -0,0 +1,230 @@
+//! # Joker Registry
+//!
+//! This module lets crates using ortalab add their own jokers by name.
+//!
+//! ortalib's `Joker` enum is closed, so a custom joker is registered with
+//! `register_joker()` as a name, its metadata and a `JokerEffect`. Once
+//! registered, round files can use the name (optionally followed by an edition,
+//! like any other joker) and it resolves through the registry. Jokers must be
+//! registered before the round files using them are read.
+//!
+//! The registry starts out with the jokers ortalab adds itself (see `history`,
+//! `deck` and `destruction`).
+//!
+//! ## Representation
+//! In a round, a custom joker is a `JokerCard` minted just for it, which keeps
+//! its place among the other jokers (for ordering, Blueprint and editions).
+//! `GameState::custom_jokers` maps that card to the registered joker, in the
+//! same way `card_extras` adds attributes to ortalib's cards.
+//!
+//! ## Key Components
+//! - `register_joker()`: Adds a custom joker to the registry
+//! - `CustomJoker`: A registered joker (name, metadata and effect)
+//! - `JokerSpec`: A joker string from a round file, built-in or custom, maybe Negative
+//!
+//! ## Negative Edition
+//! ortalib's `Edition` has no Negative, so `JokerSpec` reads it separately
+//! (`Joker Negative`) and leaves the joker card without an edition.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use enum_iterator::all;
+use indexmap::IndexMap;
+use ortalib::{Edition, Joker, JokerCard};
+use serde::Deserialize;
+
+use super::deck::deck_jokers;
+use super::destruction::destruction_jokers;
+use super::history::history_jokers;
+use super::{JokerEffect, JokerInfo, Rarity};
+
+/// A joker added through the registry
+#[derive(Clone)]
+pub struct CustomJoker {
+    pub name: Arc<str>,
+    pub info: JokerInfo,
+    pub effect: Arc<dyn JokerEffect>,
+}
+
+impl fmt::Debug for CustomJoker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomJoker")
+            .field("name", &self.name)
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Registered jokers by name, in registration order
+static REGISTRY: LazyLock<RwLock<IndexMap<String, CustomJoker>>> = LazyLock::new(|| {
+    let jokers = history_jokers()
+        .into_iter()
+        .chain(deck_jokers())
+        .chain(destruction_jokers())
+        .map(|joker| (joker.name.to_string(), joker))
+        .collect();
+    RwLock::new(jokers)
+});
+
+/// Creates one of the jokers ortalab registers itself
+pub(crate) fn bundled_joker(
+    name: &str,
+    rarity: Rarity,
+    cost: u32,
+    description: &'static str,
+    effect: impl JokerEffect + 'static,
+) -> CustomJoker {
+    CustomJoker {
+        name: name.into(),
+        info: JokerInfo {
+            rarity,
+            cost,
+            description,
+        },
+        effect: Arc::new(effect),
+    }
+}
+
+/// Adds a custom joker that round files can refer to by `name`
+///
+/// Fails if the name is empty, is already taken by a built-in or registered
+/// joker, or ends with an edition name (which would make `Name Foil` ambiguous).
+pub fn register_joker(
+    name: &str,
+    info: JokerInfo,
+    effect: Box<dyn JokerEffect>,
+) -> Result<(), String> {
+    if name.trim().is_empty() || name.trim() != name {
+        return Err(format!("Invalid custom joker name `{}`", name));
+    }
+    if name.parse::<Joker>().is_ok() {
+        return Err(format!("`{}` is already a built-in joker", name));
+    }
+    if name.ends_with(NEGATIVE_SUFFIX) {
+        return Err(format!(
+            "Custom joker name `{}` ends with the edition `Negative`",
+            name
+        ));
+    }
+    if let Some(edition) = all::<Edition>().find(|edition| name.ends_with(&edition.to_string())) {
+        return Err(format!(
+            "Custom joker name `{}` ends with the edition `{}`",
+            name, edition
+        ));
+    }
+
+    let mut registry = REGISTRY.write().map_err(|err| err.to_string())?;
+    if registry.contains_key(name) {
+        return Err(format!("`{}` is already a registered joker", name));
+    }
+    registry.insert(
+        name.to_string(),
+        CustomJoker {
+            name: name.into(),
+            info,
+            effect: effect.into(),
+        },
+    );
+    Ok(())
+}
+
+/// The registered joker called `name`, if any
+pub fn custom_joker(name: &str) -> Option<CustomJoker> {
+    REGISTRY.read().ok()?.get(name).cloned()
+}
+
+/// Every registered joker, in registration order
+pub fn custom_jokers() -> Vec<CustomJoker> {
+    REGISTRY
+        .read()
+        .map(|registry| registry.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Suffix of a joker with the Negative edition
+const NEGATIVE_SUFFIX: &str = " Negative";
+
+/// A joker as written in a round file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct JokerSpec {
+    pub kind: JokerKind,
+    /// Negative jokers take no joker slot
+    pub negative: bool,
+}
+
+/// Whether a joker is one of ortalib's or a registered one
+#[derive(Debug, Clone)]
+pub enum JokerKind {
+    Builtin(JokerCard),
+    Custom(CustomJoker, Option<Edition>),
+}
+
+impl JokerSpec {
+    /// The joker card standing for this joker in a `Round`, noting a registered
+    /// joker in `custom_jokers` under its card
+    pub fn into_joker_card(self, custom_jokers: &mut HashMap<JokerCard, CustomJoker>) -> JokerCard {
+        match self.kind {
+            JokerKind::Builtin(joker_card) => joker_card,
+            JokerKind::Custom(custom, edition) => {
+                // Any joker would do: the card only keeps the custom joker's place
+                let joker_card = JokerCard::new(Joker::Joker, edition);
+                custom_jokers.insert(joker_card, custom);
+                joker_card
+            }
+        }
+    }
+}
+
+impl FromStr for JokerSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, negative) = match s.strip_suffix(NEGATIVE_SUFFIX) {
+            Some(name) => (name, true),
+            None => (s, false),
+        };
+        let kind: JokerKind = name.parse()?;
+        if negative
+            && let JokerKind::Builtin(JokerCard {
+                edition: Some(_), ..
+            })
+            | JokerKind::Custom(_, Some(_)) = kind
+        {
+            return Err(format!("Joker `{}` has more than one edition", s));
+        }
+        Ok(JokerSpec { kind, negative })
+    }
+}
+
+impl FromStr for JokerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let builtin_err = match s.parse::<JokerCard>() {
+            Ok(joker_card) => return Ok(JokerKind::Builtin(joker_card)),
+            Err(err) => err,
+        };
+
+        let (name, edition) = all::<Edition>()
+            .find_map(|edition| {
+                s.strip_suffix(&edition.to_string())
+                    .map(|name| (name.trim(), Some(edition)))
+            })
+            .unwrap_or((s, None));
+        custom_joker(name)
+            .map(|custom| JokerKind::Custom(custom, edition))
+            .ok_or(builtin_err)
+    }
+}
+
+impl TryFrom<String> for JokerSpec {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
@@ -0,0 +1,76 @@
+//! # Levels Module
+//!
+//! This module describes the levels of the poker hands. Planet cards level up a
+//! poker hand, and every level above the first adds a fixed amount of Chips and
+//! Mult to the hand's base value.
+//!
+//! A round file may include a `hand_levels` section; hands it leaves out are at
+//! level 1:
+//!
+//! ```yaml
+//! hand_levels:
+//!   Pair: 3
+//!   Flush: 2
+//! ```
+//!
+//! ## Key Components
+//! - `HandLevels`: The `hand_levels` section of a round file
+//! - `level_up_value()`: The Chips and Mult a poker hand gains per level
+
+use std::collections::HashMap;
+
+use ortalib::{Chips, Mult, PokerHand};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::history::HandName;
+
+/// Chips and Mult a poker hand gains for each level above the first
+#[rustfmt::skip]
+pub fn level_up_value(hand: PokerHand) -> (Chips, Mult) {
+    match hand {
+        PokerHand::HighCard      => (10.0, 1.0),
+        PokerHand::Pair          => (15.0, 1.0),
+        PokerHand::TwoPair       => (20.0, 1.0),
+        PokerHand::ThreeOfAKind  => (20.0, 2.0),
+        PokerHand::Straight      => (30.0, 3.0),
+        PokerHand::Flush         => (15.0, 2.0),
+        PokerHand::FullHouse     => (25.0, 2.0),
+        PokerHand::FourOfAKind   => (30.0, 3.0),
+        PokerHand::StraightFlush => (40.0, 4.0),
+        PokerHand::FiveOfAKind   => (35.0, 3.0),
+        PokerHand::FlushHouse    => (40.0, 4.0),
+        PokerHand::FlushFive     => (50.0, 3.0),
+    }
+}
+
+/// The level of each poker hand that is above level 1
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct HandLevels(pub HashMap<HandName, u32>);
+
+impl HandLevels {
+    /// The level of `hand`
+    pub fn level(&self, hand: PokerHand) -> u32 {
+        self.0.get(&HandName(hand)).copied().unwrap_or(1)
+    }
+
+    /// The base Chips and Mult of `hand` at its level, from its `base` value at level 1
+    pub fn hand_value(&self, hand: PokerHand, base: (Chips, Mult)) -> (Chips, Mult) {
+        let (chips, mult) = base;
+        let (chips_per_level, mult_per_level) = level_up_value(hand);
+        let levels_gained = f64::from(self.level(hand) - 1);
+        (
+            chips + chips_per_level * levels_gained,
+            mult + mult_per_level * levels_gained,
+        )
+    }
+
+    /// Checks that every level is at least 1, the lowest level a hand can have
+    pub fn check(&self) -> Result<(), String> {
+        match self.0.iter().find(|&(_, &level)| level == 0) {
+            Some((hand, _)) => Err(format!("{} has level 0, but hands start at level 1", hand)),
+            None => Ok(()),
+        }
+    }
+}
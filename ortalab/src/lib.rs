@@ -0,0 +1,99 @@
+//! # Ortalab Scoring Engine
+//!
+//! This library scores poker hands according to the rules of Balatro, including
+//! the effects of jokers and card enhancements. The `ortalab` command-line tool
+//! is built on top of it.
+//!
+//! ## Key Components
+//! - `score_round()`: Scores a round read from a round file
+//! - `score_json()`: Scores a JSON round and returns its score and explain trace
+//!
+//! ## WebAssembly
+//! The engine builds for `wasm32-unknown-unknown` without the command-line tools:
+//!
+//! ```text
+//! cargo build --lib --release --target wasm32-unknown-unknown --no-default-features
+//! ```
+//!
+//! On wasm32 the `wasm` module exports `score(round_json)`, which returns the
+//! JSON of a `ScoreReport`, e.g. `{"score":648.0,"trace":["Straight (30 x 4)", ...]}`.
+
+pub mod balance;
+pub mod bench;
+pub mod consumables;
+pub mod debug;
+pub mod deck;
+pub mod economy;
+pub mod errors;
+pub mod extras;
+pub mod game;
+pub mod history;
+pub mod input;
+pub mod jokers;
+pub mod levels;
+pub mod modifiers;
+pub mod pairs;
+pub mod poker;
+pub mod progression;
+pub mod reach;
+pub mod recommend;
+pub mod replay;
+pub mod round;
+pub mod save;
+pub mod variants;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+
+use ortalib::{Chips, Mult};
+use serde::Serialize;
+
+use crate::balance::Balance;
+use crate::errors::GameResult;
+use crate::game::GameState;
+use crate::replay::Outcome;
+use crate::round::RoundInput;
+
+/// Scores a round with the scoring constants of `balance`, using its consumables first
+pub fn score_round(
+    input: RoundInput,
+    balance: Balance,
+    seed: Option<u64>,
+    explain: bool,
+) -> GameResult<(Chips, Mult)> {
+    let mut game = GameState::new(input.round, explain);
+    game.balance = balance;
+    game.card_extras = input.card_extras;
+    game.custom_jokers = input.custom_jokers;
+    game.deck_variant = input.deck_variant;
+    game.history = input.history;
+    game.hand_levels = input.hand_levels;
+    game.deck = input.deck;
+    game.remaining_deck = input.remaining_deck;
+    if let Some(seed) = seed {
+        game.seed(seed);
+    }
+    game.use_consumables(&input.consumables)?;
+    game.score()
+}
+
+/// The outcome of scoring a round together with its explain trace
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreReport {
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    pub trace: Vec<String>,
+}
+
+/// Scores a round written as JSON, collecting the explain trace instead of printing it
+pub fn score_json(round_json: &str) -> Result<ScoreReport, String> {
+    let input: RoundInput = serde_json::from_str(round_json).map_err(|err| err.to_string())?;
+    let seed = input.seed;
+
+    debug::start_recording(false);
+    let outcome = match score_round(input, Balance::default(), seed, true) {
+        Ok((chips, mult)) => Outcome::Score((chips * mult).floor()),
+        Err(err) => Outcome::Error(err.to_string()),
+    };
+    let trace = debug::finish_recording();
+    Ok(ScoreReport { outcome, trace })
+}
@@ -4,72 +4,680 @@
 //! including the effects of jokers and card enhancements.
 
 //! ## Usage
-//! The application takes a YAML file describing a round (cards, jokers, etc.)
+//! The application takes a file describing a round (cards, jokers, etc.)
 //! and outputs the final score after applying all rules and effects.
+//! Round files can be written as YAML, JSON or RON (see the `input` module),
+//! and `ortalab schema` prints the JSON Schema of the round format.
 //!
 //! ```
 //! 6991 cargo run input.yaml [--explain]
 //! ```
 //!
 //! The `--explain` flag enables detailed explanation of the scoring process.
-
-mod debug;
-mod errors;
-mod game;
-mod jokers;
-mod modifiers;
-mod poker;
+//! The `--record` flag writes a replay file of the run, which `ortalab replay`
+//! re-runs and checks step by step.
+//! The `--seed` flag enables chance-based effects such as Glass cards shattering,
+//! overriding any `seed` given in the round file.
+//! The `--balance` flag scores with the constants of a balance file (see the
+//! `balance` module) instead of Balatro's.
+//!
+//! ## Subcommands
+//! - `bench`: Checks and times the lookup-table hand evaluator against the full one
+//! - `deal`: Shuffles a deck with a seed and deals an opening hand
+//! - `economy`: Simulates money, jokers and scores against blind targets across a full run
+//! - `hand`: Shows how the played cards form their poker hand
+//! - `import`: Turns a Balatro save file into a round file
+//! - `jokers`: Lists every joker with its metadata and the fixtures that cover it
+//!   (`--show-fixtures` lists their paths)
+//! - `odds`: Shows the chance of each poker hand after the next draw from the deck
+//! - `pairs`: Writes fixtures for every ordered pair of jokers that match the reference
+//! - `progression`: Simulates runs from ante 1 to 8 with a play policy and reports how many survive
+//! - `reach`: Finds the fewest changes to a round that reach a target score
+//! - `recommend`: Ranks the jokers in the shop by the score they are expected to add
+//! - `replay`: Re-runs a replay file and fails if any step diverges
+//! - `schema`: Prints the JSON Schema of the round file format
+//! - `stats`: Shows how rule-bending jokers shift the hand types of random hands
 
 use std::{
     error::Error,
-    fs::File,
+    fs::{self, File},
     io::{Read, stdin},
     path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Args, Parser, Subcommand};
+use enum_iterator::all;
+use ortalab::{
+    bench, debug, economy, jokers, pairs, poker, progression, reach, recommend, round, save,
 };
+use ortalib::{Joker, JokerCard};
 
-use clap::Parser;
-use ortalib::{Chips, Mult, Round};
+use ortalab::balance::Balance;
+use ortalab::deck::{DEFAULT_HAND_SIZE, Deck, DeckSpec};
+use ortalab::economy::RunConfig;
+use ortalab::input::parse_input;
+use ortalab::jokers::registry::JokerSpec;
+use ortalab::progression::ProgressionConfig;
+use ortalab::replay::{ENGINE_VERSION, Outcome, Replay};
+use ortalab::round::RoundInput;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(required = true)]
+    file: Option<PathBuf>,
+
+    #[arg(long)]
+    explain: bool,
+
+    /// Seed for chance-based effects such as Glass cards shattering
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Write a replay file of the run (round, engine version, seed and explain trace)
+    #[arg(long)]
+    record: Option<PathBuf>,
+
+    /// Balance file overriding the scoring constants, e.g. `--balance tuning.toml`
+    #[arg(long)]
+    balance: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check and time the lookup-table hand evaluator against the full evaluator
+    Bench(BenchOpts),
+    /// Shuffle a deck with a seed and deal an opening hand
+    Deal(DealOpts),
+    /// Simulate money, jokers and scores across a full run
+    Economy(EconomyOpts),
+    /// Show how the played cards form their poker hand
+    Hand(HandOpts),
+    /// Turn a Balatro save file (`save.jkr`) into a round file
+    Import(ImportOpts),
+    /// List every joker with its activation, rarity, cost and fixture coverage
+    Jokers(JokersOpts),
+    /// Show the chance of each poker hand after the next draw
+    Odds(OddsOpts),
+    /// Write fixtures for every ordered pair of jokers where ortalab matches the reference
+    Pairs(PairsOpts),
+    /// Simulate runs from ante 1 to 8 and report how many survive
+    Progression(ProgressionOpts),
+    /// Find the fewest changes to a round that reach a target score
+    Reach(ReachOpts),
+    /// Rank the jokers in the shop by the score they are expected to add
+    Recommend(RecommendOpts),
+    /// Re-run a replay file and fail if any step diverges
+    Replay(ReplayOpts),
+    /// Print the JSON Schema of the round file format
+    Schema,
+    /// Show how rule-bending jokers shift the hand types of random hands
+    Stats(StatsOpts),
+}
+
+#[derive(Args, Debug)]
+struct BenchOpts {
+    /// Deck composition file whose 5-card hands are evaluated (defaults to the standard deck)
+    #[arg(long)]
+    deck: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct DealOpts {
+    /// Number of cards to deal
+    #[arg(long, default_value_t = DEFAULT_HAND_SIZE)]
+    hand_size: usize,
+
+    /// Deck composition file to deal from (defaults to the standard deck)
+    #[arg(long)]
+    deck: Option<PathBuf>,
+
+    /// Seed used to shuffle the deck
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Args, Debug)]
+struct EconomyOpts {
+    /// Run description file (seed, deck, starting jokers, shop and policy)
+    file: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct HandOpts {
+    /// Round whose played cards are evaluated with its rule-bending jokers
+    file: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct ImportOpts {
+    /// Balatro save file, e.g. `save.jkr`
+    file: PathBuf,
+
+    /// Positions (from 1) of the cards in hand to play, e.g. `--play 1,3,4`
+    /// (defaults to the highlighted cards)
+    #[arg(long, value_delimiter = ',')]
+    play: Vec<usize>,
+
+    /// Leave out jokers, enhancements and seals ortalab does not support instead of failing
+    #[arg(long)]
+    skip_unsupported: bool,
+}
+
+#[derive(Args, Debug)]
+struct JokersOpts {
+    /// Directory searched recursively for round files using each joker (repeatable)
+    #[arg(long = "fixtures", default_values = ["fixtures", "tests"])]
+    fixture_dirs: Vec<PathBuf>,
+    /// List the round files covering each joker under its row
+    #[arg(long)]
+    show_fixtures: bool,
+}
+
+#[derive(Args, Debug)]
+struct OddsOpts {
+    /// Round whose held cards are kept and whose played cards are discarded
     file: PathBuf,
 
+    /// Number of cards in hand after drawing
+    #[arg(long, default_value_t = DEFAULT_HAND_SIZE)]
+    hand_size: usize,
+
+    /// Deck composition file to draw from (defaults to the standard deck)
+    #[arg(long)]
+    deck: Option<PathBuf>,
+
+    /// Number of sampled draws when there are too many to enumerate
+    #[arg(long, default_value_t = 10_000)]
+    samples: usize,
+
+    /// Seed used for sampled draws
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Args, Debug)]
+struct PairsOpts {
+    /// Directory the fixtures are written to
+    #[arg(long, default_value = "fixtures/categorised/pairs")]
+    out: PathBuf,
+
+    /// Reference command, run as `<reference> <round file> --explain`
+    #[arg(long, default_value = "6991 ortalab")]
+    reference: String,
+
+    /// Joker to pair, e.g. `--joker Mime` (repeatable; defaults to every built-in joker)
+    #[arg(long = "joker")]
+    jokers: Vec<Joker>,
+
+    /// Representative hand to play, e.g. `--hand face_pair` (repeatable; defaults to every hand)
+    #[arg(long = "hand")]
+    hands: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct ProgressionOpts {
+    /// Progression file (seed, number of runs, deck, jokers, hands, discards and policy)
+    file: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct ReachOpts {
+    /// Round to change
+    file: PathBuf,
+
+    /// Score to reach
+    #[arg(long)]
+    target: f64,
+
+    /// Joker that may be added, e.g. `--joker Baron` (repeatable)
+    #[arg(long = "joker")]
+    pool: Vec<Joker>,
+
+    /// Most changes in one solution
+    #[arg(long, default_value_t = 2)]
+    max_changes: usize,
+
+    /// Most jokers the round may end up with, not counting Negative jokers
+    /// (defaults to the round's joker slots)
+    #[arg(long)]
+    joker_slots: Option<usize>,
+
+    /// Do not try other editions on the jokers
+    #[arg(long)]
+    no_editions: bool,
+
+    /// Do not try other enhancements on the played and held cards
+    #[arg(long)]
+    no_enhancements: bool,
+
+    /// Number of solutions to show
+    #[arg(long, default_value_t = 5)]
+    limit: usize,
+}
+
+#[derive(Args, Debug)]
+struct RecommendOpts {
+    /// Round with the current jokers, and the deck if it is not the standard one
+    file: PathBuf,
+
+    /// Joker on offer, e.g. `--candidate Baron` or `--candidate "Blueprint Foil"` (repeatable)
+    #[arg(long = "candidate", required = true)]
+    candidates: Vec<String>,
+
+    /// Number of hands sampled from the deck
+    #[arg(long, default_value_t = 100)]
+    samples: usize,
+
+    /// Number of cards in each sampled hand
+    #[arg(long, default_value_t = DEFAULT_HAND_SIZE)]
+    hand_size: usize,
+
+    /// Seed used for sampled hands
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Args, Debug)]
+struct ReplayOpts {
+    /// Replay file written with `--record`
+    file: PathBuf,
+
+    /// Show the explanation while re-running
     #[arg(long)]
     explain: bool,
 }
 
+#[derive(Args, Debug)]
+struct StatsOpts {
+    /// Number of cards in each random hand
+    #[arg(long, default_value_t = 5)]
+    hand_size: usize,
+
+    /// Deck composition file to deal from (defaults to the standard deck)
+    #[arg(long)]
+    deck: Option<PathBuf>,
+
+    /// Joker to compare against no jokers, e.g. `--joker Shortcut` (repeatable)
+    #[arg(long = "joker")]
+    jokers: Vec<Joker>,
+
+    /// Number of sampled hands when there are too many to enumerate
+    #[arg(long, default_value_t = 10_000)]
+    samples: usize,
+
+    /// Seed used for sampled hands
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let opts = Opts::parse();
-    let round = parse_round(&opts)?;
 
-    let (_chips, _mult) = score(round, opts.explain);
-    Ok(())
+    match opts.command {
+        Some(Command::Bench(bench_opts)) => bench(&bench_opts),
+        Some(Command::Deal(deal_opts)) => deal(&deal_opts),
+        Some(Command::Economy(economy_opts)) => economy(&economy_opts),
+        Some(Command::Hand(hand_opts)) => hand(&hand_opts),
+        Some(Command::Import(import_opts)) => import(&import_opts),
+        Some(Command::Jokers(jokers_opts)) => jokers(&jokers_opts),
+        Some(Command::Odds(odds_opts)) => odds(&odds_opts),
+        Some(Command::Pairs(pairs_opts)) => pair_fixtures(&pairs_opts),
+        Some(Command::Progression(progression_opts)) => progression(&progression_opts),
+        Some(Command::Reach(reach_opts)) => reach(&reach_opts),
+        Some(Command::Recommend(recommend_opts)) => recommend(&recommend_opts),
+        Some(Command::Replay(replay_opts)) => replay(&replay_opts),
+        Some(Command::Schema) => schema(),
+        Some(Command::Stats(stats_opts)) => stats(&stats_opts),
+        None => {
+            // `file` is required whenever no subcommand is given
+            let file = opts.file.ok_or("missing round file")?;
+            let contents = read_input(&file)?;
+            let input: RoundInput = parse_input(&file, &contents)?;
+            let seed = opts.seed.or(input.seed);
+            let balance_contents = match &opts.balance {
+                Some(path) => Some(parse_input::<serde_json::Value>(path, &read_input(path)?)?),
+                None => None,
+            };
+            let balance = parse_balance(balance_contents.clone())?;
+
+            let Some(record) = opts.record else {
+                score(input, balance, seed, opts.explain);
+                return Ok(());
+            };
+
+            // The round is kept as read, whatever format it was written in
+            let round: serde_json::Value = parse_input(&file, &contents)?;
+            debug::start_recording(opts.explain);
+            let outcome = score(input, balance, seed, true);
+            let replay = Replay {
+                version: ENGINE_VERSION.to_string(),
+                seed,
+                round,
+                balance: balance_contents,
+                trace: debug::finish_recording(),
+                outcome,
+            };
+            fs::write(record, serde_json::to_string_pretty(&replay)?)?;
+            Ok(())
+        }
+    }
 }
 
-fn parse_round(opts: &Opts) -> Result<Round, Box<dyn Error>> {
+fn read_input(path: &Path) -> Result<String, Box<dyn Error>> {
     let mut input = String::new();
-    if opts.file == Path::new("-") {
+    if path == Path::new("-") {
         stdin().read_to_string(&mut input)?;
     } else {
-        File::open(&opts.file)?.read_to_string(&mut input)?;
+        File::open(path)?.read_to_string(&mut input)?;
+    }
+    Ok(input)
+}
+
+fn parse_round(path: &Path) -> Result<RoundInput, Box<dyn Error>> {
+    parse_input(path, &read_input(path)?)
+}
+
+/// The balance of a balance file as read, or Balatro's constants without one
+fn parse_balance(contents: Option<serde_json::Value>) -> Result<Balance, Box<dyn Error>> {
+    let Some(contents) = contents else {
+        return Ok(Balance::default());
+    };
+    let balance: Balance =
+        serde_json::from_value(contents).map_err(|err| format!("invalid balance file: {}", err))?;
+    balance.check()?;
+    Ok(balance)
+}
+
+fn parse_deck(path: Option<&Path>) -> Result<Deck, Box<dyn Error>> {
+    let spec: DeckSpec = match path {
+        Some(path) => parse_input(path, &read_input(path)?)?,
+        None => DeckSpec::default(),
+    };
+    Ok(Deck::from_spec(&spec)?)
+}
+
+fn bench(opts: &BenchOpts) -> Result<(), Box<dyn Error>> {
+    let deck = parse_deck(opts.deck.as_deref())?;
+
+    let mut mismatches = 0;
+    for comparison in bench::compare_evaluators(deck.cards())? {
+        println!("{}", comparison);
+        mismatches += comparison.mismatches;
+    }
+    if mismatches > 0 {
+        return Err(format!("evaluators disagree on {} hands", mismatches).into());
+    }
+    Ok(())
+}
+
+fn deal(opts: &DealOpts) -> Result<(), Box<dyn Error>> {
+    let mut deck = parse_deck(opts.deck.as_deref())?;
+    deck.shuffle(opts.seed);
+
+    let mut hand = Vec::new();
+    deck.draw_to_hand_size(&mut hand, opts.hand_size);
+    for card in &hand {
+        println!("{}", card);
+    }
+    Ok(())
+}
+
+fn economy(opts: &EconomyOpts) -> Result<(), Box<dyn Error>> {
+    let config: RunConfig = parse_input(&opts.file, &read_input(&opts.file)?)?;
+    let mut policy = config.policy.create();
+
+    for ante in economy::simulate_run(&config, policy.as_mut())? {
+        println!("{}", ante);
+    }
+    Ok(())
+}
+
+fn hand(opts: &HandOpts) -> Result<(), Box<dyn Error>> {
+    let round = parse_round(&opts.file)?.round;
+    let (four_fingers, shortcut, smeared) = poker::active_rules(&round.jokers);
+
+    let evaluation = poker::evaluate_hand(&round.cards_played, four_fingers, shortcut, smeared);
+    print!("{}", evaluation);
+    Ok(())
+}
+
+fn import(opts: &ImportOpts) -> Result<(), Box<dyn Error>> {
+    let options = save::ImportOptions {
+        play: opts.play.clone(),
+        skip_unsupported: opts.skip_unsupported,
+    };
+    let imported = save::import_save(&fs::read(&opts.file)?, &options)?;
+    for skipped in &imported.skipped {
+        eprintln!("Warning: left out {}", skipped);
+    }
+    print!("{}", imported.to_yaml()?);
+    Ok(())
+}
+
+fn jokers(opts: &JokersOpts) -> Result<(), Box<dyn Error>> {
+    let mut catalogue = jokers::catalogue::build_catalogue(&opts.fixture_dirs);
+    catalogue.show_fixtures = opts.show_fixtures;
+    print!("{}", catalogue);
+    Ok(())
+}
+
+fn odds(opts: &OddsOpts) -> Result<(), Box<dyn Error>> {
+    let round = parse_round(&opts.file)?.round;
+    let mut deck = parse_deck(opts.deck.as_deref())?;
+
+    // Neither the kept cards nor the discarded cards can be drawn again
+    deck.remove_all(&round.cards_held_in_hand);
+    deck.remove_all(&round.cards_played);
+
+    let odds = deck.hand_odds(
+        &round.cards_held_in_hand,
+        opts.hand_size,
+        &round.jokers,
+        opts.samples,
+        opts.seed,
+    )?;
+    print!("{}", odds);
+    Ok(())
+}
+
+fn pair_fixtures(opts: &PairsOpts) -> Result<(), Box<dyn Error>> {
+    let jokers: Vec<Joker> = if opts.jokers.is_empty() {
+        all::<Joker>().collect()
+    } else {
+        opts.jokers.clone()
+    };
+    let hands = pairs::representative_hands(&opts.hands)?;
+    let mut reference = opts.reference.split_whitespace();
+    let program = reference.next().ok_or("the reference command is empty")?;
+    let reference_args: Vec<&str> = reference.collect();
+    let created_out = !opts.out.exists();
+    fs::create_dir_all(&opts.out)?;
+
+    let mut results = pairs::PairResults::default();
+    for round in pairs::pair_rounds(&jokers, &hands) {
+        let name = round.file_stem();
+        let path = opts.out.join(format!("{}.yml", name));
+        fs::write(&path, round.to_yaml()?)?;
+
+        let reference_output = match process::Command::new(program)
+            .args(&reference_args)
+            .arg(&path)
+            .arg("--explain")
+            .output()
+        {
+            Ok(output) => output,
+            Err(err) => {
+                fs::remove_file(&path)?;
+                // Leave no empty fixture directory behind, unless it was there before;
+                // fails harmlessly if it has files
+                if created_out {
+                    let _ = fs::remove_dir(&opts.out);
+                }
+                return Err(
+                    format!("could not run reference `{}`: {}", opts.reference, err).into(),
+                );
+            }
+        };
+        let expected = String::from_utf8_lossy(&reference_output.stdout);
+        let checked = if reference_output.status.success() {
+            round.explained_output()
+        } else {
+            Err(format!(
+                "reference {} {}",
+                reference_output.status,
+                String::from_utf8_lossy(&reference_output.stderr).trim()
+            )
+            .trim_end()
+            .to_string())
+        };
+
+        // Only rounds where both scorers agree are kept as fixtures
+        match checked {
+            Ok(ours) if pairs::same_output(&ours, &expected) => {
+                fs::write(path.with_extension("output"), expected.as_bytes())?;
+                results.written += 1;
+            }
+            Ok(ours) => {
+                fs::remove_file(&path)?;
+                results.mismatches.push(pairs::PairMismatch {
+                    round: name,
+                    difference: pairs::first_difference(&ours, &expected),
+                });
+            }
+            Err(err) => {
+                fs::remove_file(&path)?;
+                results.errors.push((name, err));
+            }
+        }
     }
+    print!("{}", results);
+    Ok(())
+}
+
+fn progression(opts: &ProgressionOpts) -> Result<(), Box<dyn Error>> {
+    let config: ProgressionConfig = parse_input(&opts.file, &read_input(&opts.file)?)?;
 
-    let round = serde_yaml::from_str(&input)?;
-    Ok(round)
+    print!("{}", progression::survival(&config)?);
+    Ok(())
+}
+
+fn reach(opts: &ReachOpts) -> Result<(), Box<dyn Error>> {
+    let input = parse_round(&opts.file)?;
+    let space = reach::SearchSpace {
+        editions: !opts.no_editions,
+        enhancements: !opts.no_enhancements,
+        pool: opts.pool.clone(),
+        joker_slots: opts.joker_slots.unwrap_or(input.joker_slots),
+        max_changes: opts.max_changes,
+    };
+
+    let result = reach::reach(&input, opts.target, &space)?;
+    println!(
+        "Current score: {} (target {})",
+        result.current, result.target
+    );
+    if result.current >= result.target {
+        println!("The round already reaches the target");
+        return Ok(());
+    }
+    let Some(best) = result.solutions.first() else {
+        return Err(format!(
+            "no combination of up to {} changes reaches {} ({} tried)",
+            opts.max_changes, opts.target, result.tried
+        )
+        .into());
+    };
+
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    println!(
+        "{} solution{} with {} change{} ({} tried)",
+        result.solutions.len(),
+        plural(result.solutions.len()),
+        best.changes.len(),
+        plural(best.changes.len()),
+        result.tried
+    );
+    for solution in result.solutions.iter().take(opts.limit) {
+        print!("{}", solution);
+    }
+    Ok(())
+}
+
+fn recommend(opts: &RecommendOpts) -> Result<(), Box<dyn Error>> {
+    let input = parse_round(&opts.file)?;
+    let candidates = opts
+        .candidates
+        .iter()
+        .map(|candidate| candidate.parse())
+        .collect::<Result<Vec<JokerSpec>, String>>()?;
+    let options = recommend::RecommendOptions {
+        samples: opts.samples,
+        hand_size: opts.hand_size,
+        seed: opts.seed,
+    };
+
+    let recommendations = recommend::recommend(&input, candidates, &options)?;
+    print!("{}", recommendations);
+    Ok(())
+}
+
+fn replay(opts: &ReplayOpts) -> Result<(), Box<dyn Error>> {
+    let replay: Replay = parse_input(&opts.file, &read_input(&opts.file)?)?;
+    if replay.version != ENGINE_VERSION {
+        eprintln!(
+            "Warning: replay recorded with ortalab {}, re-running with {}",
+            replay.version, ENGINE_VERSION
+        );
+    }
+    let input: RoundInput = serde_json::from_value(replay.round.clone())?;
+    let balance = parse_balance(replay.balance.clone())?;
+
+    debug::start_recording(opts.explain);
+    let outcome = score(input, balance, replay.seed, true);
+    let trace = debug::finish_recording();
+
+    replay
+        .check(&trace, &outcome)
+        .map_err(|divergence| divergence.to_string())?;
+    println!("Replay matches: {} steps, {}", trace.len(), outcome);
+    Ok(())
+}
+
+fn schema() -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string_pretty(&round::round_schema())?);
+    Ok(())
+}
+
+fn stats(opts: &StatsOpts) -> Result<(), Box<dyn Error>> {
+    let deck = parse_deck(opts.deck.as_deref())?;
+    let jokers: Vec<JokerCard> = opts
+        .jokers
+        .iter()
+        .map(|&joker| JokerCard::new(joker, None))
+        .collect();
+
+    let stats = deck.hand_stats(opts.hand_size, &jokers, opts.samples, opts.seed)?;
+    print!("{}", stats);
+    Ok(())
 }
 
-fn score(round: Round, explain: bool) -> (Chips, Mult) {
-    let mut game = game::GameState::new(round, explain);
-    let result = game.score();
+fn score(input: RoundInput, balance: Balance, seed: Option<u64>, explain: bool) -> Outcome {
+    let result = ortalab::score_round(input, balance, seed, explain);
 
     match result {
         Ok((chips, mult)) => {
-            println!("{}", (chips * mult).floor());
-            (chips, mult)
+            let score = (chips * mult).floor();
+            println!("{}", score);
+            Outcome::Score(score)
         }
         Err(e) => {
             eprintln!("Game error: {}", e);
-            (0.0, 0.0)
+            Outcome::Error(e.to_string())
         }
     }
 }
@@ -1,38 +1,62 @@
 //! # Modifiers Module
 //!
 //! This module handles card modifiers such as enhancements and editions.
-//! It provides functions to apply various card modifiers to the game state.
+//! It provides functions to apply various card modifiers to the game state,
+//! with the numbers of the round's balance (see the `balance` module).
 
+use crate::balance::{EditionValues, EnhancementValues};
 use crate::errors::GameResult;
+use crate::extras::CardLabel;
 use ortalib::{Card, Chips, Edition, Enhancement, Mult};
+use rand::Rng;
 
 use crate::explain_dbg_bool;
 
+/// Chance that a scored Glass card is destroyed after scoring
+pub const GLASS_SHATTER_CHANCE: f64 = 0.25;
+
 /// Applies enhancement effects to the game state
 pub fn apply_enhancement(
     card: &Card,
+    label: CardLabel,
+    values: &EnhancementValues,
     chips: &mut Chips,
     mult: &mut Mult,
     explain_enabled: bool,
 ) -> GameResult<()> {
     match card.enhancement {
         Some(Enhancement::Bonus) => {
-            *chips += 30.0;
+            *chips += values.bonus;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} +30 Chips ({} x {})",
-                card,
+                "{} +{} Chips ({} x {})",
+                label,
+                values.bonus,
                 *chips,
                 *mult
             );
         }
         Some(Enhancement::Mult) => {
-            *mult += 4.0;
-            explain_dbg_bool!(explain_enabled, "{} +4 Mult ({} x {})", card, *chips, *mult);
+            *mult += values.mult;
+            explain_dbg_bool!(
+                explain_enabled,
+                "{} +{} Mult ({} x {})",
+                label,
+                values.mult,
+                *chips,
+                *mult
+            );
         }
         Some(Enhancement::Glass) => {
-            *mult *= 2.0;
-            explain_dbg_bool!(explain_enabled, "{} x2 Mult ({} x {})", card, *chips, *mult);
+            *mult *= values.glass;
+            explain_dbg_bool!(
+                explain_enabled,
+                "{} x{} Mult ({} x {})",
+                label,
+                values.glass,
+                *chips,
+                *mult
+            );
         }
         Some(Enhancement::Steel) => {
             // Steel enhancement is handled in apply_steel_enhancement function
@@ -45,40 +69,50 @@ pub fn apply_enhancement(
     Ok(())
 }
 
+/// Rolls whether a scored Glass card shatters
+pub fn glass_shatters(rng: &mut impl Rng) -> bool {
+    rng.random_bool(GLASS_SHATTER_CHANCE)
+}
+
 /// Applies edition effects to the game state
 pub fn apply_edition(
     card: &Card,
+    label: CardLabel,
+    values: &EditionValues,
     chips: &mut Chips,
     mult: &mut Mult,
     explain_enabled: bool,
 ) -> GameResult<()> {
     match card.edition {
         Some(Edition::Foil) => {
-            *chips += 50.0;
+            *chips += values.foil;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} +50 Chips ({} x {})",
-                card,
+                "{} +{} Chips ({} x {})",
+                label,
+                values.foil,
                 *chips,
                 *mult
             );
         }
         Some(Edition::Holographic) => {
-            *mult += 10.0;
+            *mult += values.holographic;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} +10 Mult ({} x {})",
-                card,
+                "{} +{} Mult ({} x {})",
+                label,
+                values.holographic,
                 *chips,
                 *mult
             );
         }
         Some(Edition::Polychrome) => {
-            *mult *= 1.5;
+            *mult *= values.polychrome;
             explain_dbg_bool!(
                 explain_enabled,
-                "{} x1.5 Mult ({} x {})",
-                card,
+                "{} x{} Mult ({} x {})",
+                label,
+                values.polychrome,
                 *chips,
                 *mult
             );
@@ -91,16 +125,19 @@ pub fn apply_edition(
 /// Applies Steel enhancement effects for cards held in hand
 pub fn apply_steel_enhancement(
     card: &Card,
+    label: CardLabel,
+    values: &EnhancementValues,
     chips: &mut Chips,
     mult: &mut Mult,
     explain_enabled: bool,
 ) -> GameResult<()> {
     if let Some(Enhancement::Steel) = card.enhancement {
-        *mult *= 1.5;
+        *mult *= values.steel;
         explain_dbg_bool!(
             explain_enabled,
-            "{} x1.5 Mult ({} x {})",
-            card,
+            "{} x{} Mult ({} x {})",
+            label,
+            values.steel,
             *chips,
             *mult
         );
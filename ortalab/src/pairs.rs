@@ -0,0 +1,229 @@
+//! # Pairs Module
+//!
+//! This module builds the rounds of the pairwise joker fixtures.
+//!
+//! The hardest scoring bugs come from two jokers interacting (Blueprint next to
+//! Mime, Pareidolia with Sock and Buskin, Smeared Joker with Flower Pot), so
+//! every ordered pair of built-in jokers is played over a few representative
+//! hands. `ortalab pairs` scores each round with ortalab and with the reference
+//! implementation, and only the rounds where both agree become fixtures under
+//! `fixtures/categorised/pairs/`. Generating them needs the reference installed;
+//! without it `ortalab pairs` stops at the first round and leaves nothing behind.
+//!
+//! Jokers added through the registry are left out, since the reference does not
+//! know them.
+//!
+//! ## Key Components
+//! - `REPRESENTATIVE_HANDS`: The played and held cards every pair is tried with
+//! - `pair_rounds()`: Every ordered pair of jokers over a set of hands
+//! - `PairRound`: One generated round, with its file name and explained output
+//! - `PairResults`: The fixtures written and the rounds where the scorers disagree
+
+use std::fmt;
+
+use ortalib::{Joker, JokerCard};
+use serde::Serialize;
+
+use crate::balance::Balance;
+use crate::debug;
+use crate::round::RoundInput;
+
+/// Played and held cards that a pair of jokers is tried with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepresentativeHand {
+    /// Used in fixture file names
+    pub name: &'static str,
+    pub cards_played: &'static [&'static str],
+    pub cards_held_in_hand: &'static [&'static str],
+}
+
+/// Hands chosen so most jokers have something to act on: face cards, Kings held
+/// in hand, all four suits, Fibonacci ranks, and straights and flushes that only
+/// exist under Four Fingers, Shortcut or Smeared Joker
+#[rustfmt::skip]
+pub const REPRESENTATIVE_HANDS: [RepresentativeHand; 4] = [
+    RepresentativeHand { name: "face_pair",       cards_played: &["K♥", "K♣", "J♦"],             cards_held_in_hand: &["K♠", "Q♥", "3♣"] },
+    RepresentativeHand { name: "four_suits",      cards_played: &["Q♦", "Q♥", "8♠", "8♣", "3♦"], cards_held_in_hand: &["2♠", "10♣"] },
+    RepresentativeHand { name: "gapped_straight", cards_played: &["3♠", "5♣", "7♠", "9♠", "J♠"], cards_held_in_hand: &["A♥"] },
+    RepresentativeHand { name: "red_four_flush",  cards_played: &["A♥", "2♦", "5♥", "8♦", "6♣"], cards_held_in_hand: &["4♥", "4♦"] },
+];
+
+/// Looks up representative hands by name; no names selects every hand
+pub fn representative_hands(names: &[String]) -> Result<Vec<RepresentativeHand>, String> {
+    if names.is_empty() {
+        return Ok(REPRESENTATIVE_HANDS.to_vec());
+    }
+    names
+        .iter()
+        .map(|name| {
+            REPRESENTATIVE_HANDS
+                .iter()
+                .find(|hand| hand.name == name)
+                .copied()
+                .ok_or_else(|| {
+                    let known: Vec<&str> =
+                        REPRESENTATIVE_HANDS.iter().map(|hand| hand.name).collect();
+                    format!(
+                        "unknown hand `{}` (expected one of {})",
+                        name,
+                        known.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+/// A round file as written by the generator
+#[derive(Serialize)]
+struct PairRoundFile {
+    cards_played: Vec<String>,
+    cards_held_in_hand: Vec<String>,
+    jokers: Vec<String>,
+}
+
+/// Two jokers, in order, played over one representative hand
+#[derive(Debug, Clone, Copy)]
+pub struct PairRound {
+    pub jokers: [Joker; 2],
+    pub hand: RepresentativeHand,
+}
+
+/// Every ordered pair of `jokers`, a joker paired with itself included, over every hand
+pub fn pair_rounds(jokers: &[Joker], hands: &[RepresentativeHand]) -> Vec<PairRound> {
+    let mut rounds = Vec::new();
+    for &first in jokers {
+        for &second in jokers {
+            for &hand in hands {
+                rounds.push(PairRound {
+                    jokers: [first, second],
+                    hand,
+                });
+            }
+        }
+    }
+    rounds
+}
+
+/// A joker's name as used in file names, e.g. `sock_and_buskin`
+fn snake_case(joker: Joker) -> String {
+    JokerCard::new(joker, None)
+        .to_string()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+impl PairRound {
+    /// The fixture's file name without its extension, e.g. `blueprint_mime_face_pair`
+    pub fn file_stem(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            snake_case(self.jokers[0]),
+            snake_case(self.jokers[1]),
+            self.hand.name
+        )
+    }
+
+    /// The round file's contents
+    pub fn to_yaml(&self) -> Result<String, String> {
+        let file = PairRoundFile {
+            cards_played: self
+                .hand
+                .cards_played
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+            cards_held_in_hand: self
+                .hand
+                .cards_held_in_hand
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+            jokers: self
+                .jokers
+                .iter()
+                .map(|&joker| JokerCard::new(joker, None).to_string())
+                .collect(),
+        };
+        serde_yaml::to_string(&file).map_err(|err| err.to_string())
+    }
+
+    /// What `ortalab --explain` prints for the round: the explain trace, then the score
+    pub fn explained_output(&self) -> Result<String, String> {
+        let input: RoundInput =
+            serde_yaml::from_str(&self.to_yaml()?).map_err(|err| err.to_string())?;
+        let seed = input.seed;
+
+        debug::start_recording(false);
+        let result = crate::score_round(input, Balance::default(), seed, true);
+        let mut lines = debug::finish_recording();
+        let (chips, mult) = result.map_err(|err| err.to_string())?;
+        lines.push((chips * mult).floor().to_string());
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+/// Whether two outputs agree, ignoring whitespace like `ortalab_test.sh` does
+pub fn same_output(ours: &str, reference: &str) -> bool {
+    let strip = |output: &str| output.split_whitespace().collect::<String>();
+    strip(ours) == strip(reference)
+}
+
+/// The first line where two outputs differ, as (ours, reference)
+pub fn first_difference(ours: &str, reference: &str) -> (String, String) {
+    let mut ours = ours.lines().map(str::trim).filter(|line| !line.is_empty());
+    let mut reference = reference
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    loop {
+        match (ours.next(), reference.next()) {
+            (Some(a), Some(b)) if a == b => continue,
+            (a, b) => {
+                let show = |line: Option<&str>| line.unwrap_or("<end of output>").to_string();
+                return (show(a), show(b));
+            }
+        }
+    }
+}
+
+/// A round where ortalab and the reference disagree
+#[derive(Debug, Clone)]
+pub struct PairMismatch {
+    pub round: String,
+    /// The first differing line, as (ours, reference)
+    pub difference: (String, String),
+}
+
+/// The outcome of generating the pairwise fixtures
+#[derive(Debug, Clone, Default)]
+pub struct PairResults {
+    /// Fixtures written, where ortalab matches the reference
+    pub written: usize,
+    /// Rounds left out because the scorers disagree
+    pub mismatches: Vec<PairMismatch>,
+    /// Rounds left out because either scorer failed, with the error
+    pub errors: Vec<(String, String)>,
+}
+
+impl fmt::Display for PairResults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mismatch in &self.mismatches {
+            let (ours, reference) = &mismatch.difference;
+            writeln!(f, "Mismatch: {}", mismatch.round)?;
+            writeln!(f, "  ortalab:   {}", ours)?;
+            writeln!(f, "  reference: {}", reference)?;
+        }
+        for (round, error) in &self.errors {
+            writeln!(f, "Error: {}: {}", round, error)?;
+        }
+        writeln!(
+            f,
+            "{} fixtures written, {} mismatches, {} errors",
+            self.written,
+            self.mismatches.len(),
+            self.errors.len()
+        )
+    }
+}
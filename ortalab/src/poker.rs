@@ -1,968 +1,620 @@
 //! # Poker Module
 //!
-//! This module contains functions for poker hand analysis and identification.
+//! This module contains poker hand evaluation.
 //!
 //! ## Key Components
-//! - `identify_hand()`: Determines the poker hand type from a set of cards
-//! - `get_scoring_cards()`: Identifies which cards contribute to scoring
-//! - `analyse_hand_conditions()`: Analyses hand for specific conditions (pairs, straights, etc.)
+//! - `evaluate_hand()`: Evaluates played cards into a `HandEvaluation`
+//! - `HandEvaluation`: The poker hand together with its canonical breakdown
+//!   (scoring cards, kickers, the rule modifiers it needed and the hand conditions)
+//! - `identify_hand()`: Allocation-free lookup-table evaluator for when only the
+//!   poker hand type is needed
+//! - `counts_as_suit()`: The suits a card counts as, for hands and jokers alike
+//! - `SuitAssignment`: A card counted as a suit other than its own
+//! - `cover_every_suit()`: Assigns cards to the four suits (Flower Pot)
 //!
-//! ## Hand Analysis
-//! The module supports standard poker hand analysis as well as special cases:
-//! - Shortcut joker effects (allowing straights with gaps)
-//! - Four Fingers joker effects (allowing 4-card hands)
-//! - Smeared Joker effects (treating cards as having both suits of the same color)
+//! Every question about a hand (its type, which cards score, what it contains)
+//! is answered by the same evaluation, so the answers can never disagree.
+//! `identify_hand()` is the one exception: it is a fast path for solvers and
+//! probability queries, checked against `evaluate_hand()` by `ortalab bench`.
 //!
-//! ## Helper Functions
-//! Various helper functions support the analysis of specific hand types:
-//! - Straight detection (regular and shortcut)
-//! - Flush detection
-//! - Pair/Three-of-a-kind/etc. detection
-
-use crate::errors::GameResult;
-use enum_iterator::Sequence;
-use indexmap::IndexMap;
-use ortalib::{Card, PokerHand, Rank, Suit};
-
-/// Returns a IndexMap mapping each rank to the number of cards with that rank in played cards
-/// For example, if five 10s are played, the result will be {10: 5}
-fn group_rank(cards: &[Card]) -> IndexMap<Rank, usize> {
-    let mut counts = IndexMap::new();
-    for card in cards {
-        *counts.entry(card.rank).or_insert(0) += 1;
-    }
-    counts
-}
-
-/// Returns a IndexMap mapping each rank to the cards with that rank in played cards
-/// For example, if five 10s are played, the result will be {10: [10♥, 10♠, 10♦, 10♣, 10♥]}
-fn group_by_rank(cards: &[Card]) -> IndexMap<Rank, Vec<&Card>> {
-    let mut groups = IndexMap::new();
-    for card in cards {
-        groups.entry(card.rank).or_insert_with(Vec::new).push(card);
-    }
-    groups
-}
-
-/// Returns a IndexMap mapping each suit to the number of cards with that suit in played cards
-/// For example, if five 10s are played, the result will be {♠: [10♠], ♣: [10♣], ♥: [10♥, 10♥], ♦: [10♦]}
-fn group_by_suit(cards: &[Card], smeared_joker_active: bool) -> IndexMap<Suit, Vec<&Card>> {
-    let mut suit_cards: IndexMap<Suit, Vec<&Card>> = IndexMap::new();
-
-    // if there are any wild cards, we need to count them as all suits
-    for card in cards {
-        if card.enhancement == Some(ortalib::Enhancement::Wild) {
-            for suit in [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
-                suit_cards.entry(suit).or_default().push(card);
-            }
-        } else if smeared_joker_active {
-            // With Smeared Joker, add the card to its own suit and the suit of the same color
-            suit_cards.entry(card.suit).or_default().push(card);
-            let same_color_suit = card.suit.other_suit_of_same_color();
-            suit_cards.entry(same_color_suit).or_default().push(card);
-        } else {
-            suit_cards.entry(card.suit).or_default().push(card);
-        }
-    }
-
-    suit_cards
-}
-
-/// Determines if the cards form a flush (all cards of the same suit)
-fn is_flush(cards: &[Card], smeared_joker_active: bool) -> bool {
-    if cards.len() < 5 {
-        return false;
-    }
+//! ## Rule Modifiers
+//! Some jokers bend the rules used to form hands:
+//! - Four Fingers: Flushes and Straights can be made with 4 cards
+//! - Shortcut: Straights can be made with gaps of 1 rank
+//! - Smeared Joker: Suits of the same color count as the same suit
+//!
+//! Wild cards count as every suit. A hand is always formed with as few rule
+//! modifiers as possible, so `HandEvaluation::modifiers` only lists the jokers
+//! that the hand actually needed.
+//!
+//! ## Suit Resolution
+//! A Wild card, or a card under Smeared Joker, can count as more than one suit.
+//! Where a card has to be counted as exactly one suit, the choice is made
+//! explicit: a Flush counts all its cards as the Flush's suit, and Flower Pot
+//! counts each card as a different suit. Cards counted as a suit other than
+//! their own are listed as `SuitAssignment`s, which the explain output reports
+//! (e.g. `7♦ Wild counted as ♠ for Flush`).
+//!
+//! Jokers that look for a suit (Greedy Joker, Blackboard) deliberately do not
+//! use these assignments. They accept any suit the card counts as, through
+//! `counts_as_suit()`, so one Wild card counts for every suit joker, as in
+//! Balatro, whatever suit the Flush counted it as. The suit jokers report each
+//! such card on its own line, so a Wild 7♠ in a Diamond Flush can show both
+//! `7♠ Wild counted as ♦ for Flush` and `7♠ Wild counted as ♥ for Lusty Joker`.
+//!
+//! ## Ordering
+//! Scoring cards and kickers keep the order the cards were played in.
 
-    let suit_groups = group_by_suit(cards, smeared_joker_active);
+use std::fmt;
 
-    // Check if any suit has enough cards for a flush
-    suit_groups.values().any(|suit_cards| suit_cards.len() >= 5)
-}
+use enum_iterator::all;
+use indexmap::IndexMap;
+use itertools::Itertools;
+use ortalib::{Card, Enhancement, Joker, JokerCard, PokerHand, Rank, Suit};
 
-/// Determines if the cards form a straight (consecutive ranks)
-fn is_straight(cards: &[Card]) -> bool {
-    if cards.len() < 5 {
-        return false; // Not enough cards for a straight
-    }
+use crate::errors::GameResult;
 
-    let mut ranks: Vec<Rank> = cards.iter().map(|card| card.rank).collect();
-    ranks.sort();
-
-    // Special case: check for A-5 straight (Ace is low)
-    if ranks[0] == Rank::Two
-        && ranks[1] == Rank::Three
-        && ranks[2] == Rank::Four
-        && ranks[3] == Rank::Five
-        && ranks[4] == Rank::Ace
-    {
-        return true;
-    }
+/// Number of cards needed for a Flush or Straight
+const FULL_HAND_SIZE: usize = 5;
 
-    for i in 0..ranks.len() - 1 {
-        if let Some(next_rank) = ranks[i].next() {
-            if next_rank != ranks[i + 1] {
-                return false;
-            }
-        } else {
-            return false;
-        }
-    }
+/// Number of cards needed for a Flush or Straight with Four Fingers
+const FOUR_FINGERS_HAND_SIZE: usize = 4;
 
-    true
+/// Hand conditions that jokers can check for, whatever the poker hand is
+/// (e.g. a Full House contains a Pair and Three of a Kind)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HandConditions {
+    pub contains_pair: bool,
+    pub contains_two_pair: bool,
+    pub contains_three_of_a_kind: bool,
+    pub contains_straight: bool,
+    pub contains_flush: bool,
 }
 
-/// Checks if the played cards form a 3+2 pattern (three cards of one rank, two of another)
-/// This is used to identify Full House and Flush House
-fn has_three_two_pattern(cards: &[Card]) -> bool {
-    if cards.len() < 5 {
-        return false;
-    }
-
-    let rank_counts = group_rank(cards);
-
-    // Need exactly 2 different ranks
-    if rank_counts.len() != 2 {
-        return false;
-    }
-
-    // Check if there's a 3-2 distribution
-    let counts: Vec<usize> = rank_counts.values().copied().collect();
-    counts.contains(&3) && counts.contains(&2)
+/// A poker hand together with the cards that form it
+#[derive(Debug, Clone)]
+pub struct HandEvaluation {
+    /// The best poker hand formed by the cards
+    pub hand: PokerHand,
+    /// Cards that form the hand, in played order
+    pub scoring_cards: Vec<Card>,
+    /// Played cards that are not part of the hand, in played order
+    pub kickers: Vec<Card>,
+    /// Rule-bending jokers the hand could not have been formed without
+    pub modifiers: Vec<Joker>,
+    /// Conditions contained in the cards, used by jokers
+    pub conditions: HandConditions,
+    /// Cards the hand counts as a suit other than their own, in played order
+    pub suit_assignments: Vec<SuitAssignment>,
 }
 
-/// Check if there's a 4-card flush in the hand
-fn has_four_card_flush(cards: &[Card], smeared_joker_active: bool) -> bool {
-    if cards.len() < 4 {
-        return false;
-    }
-    let suit_groups = group_by_suit(cards, smeared_joker_active);
-
-    // Check if any suit appears at least 4 times
-    suit_groups.values().any(|cards| cards.len() >= 4)
+/// A card counted as a suit other than its own, by being Wild or by Smeared Joker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuitAssignment {
+    pub card: Card,
+    /// The suit the card is counted as
+    pub suit: Suit,
 }
 
-/// Check if there's a 4-card straight in the hand
-fn has_four_card_straight(cards: &[Card]) -> bool {
-    if cards.len() < 4 {
-        return false;
-    }
-
-    let mut ranks: Vec<Rank> = cards.iter().map(|card| card.rank).collect();
-    ranks.sort();
-    ranks.dedup();
-
-    // If we don't have at least 4 unique ranks, no straight is possible
-    if ranks.len() < 4 {
-        return false;
-    }
-
-    // Special case: check for A-4 straight (Ace is low)
-    if ranks.contains(&Rank::Ace)
-        && ranks.contains(&Rank::Two)
-        && ranks.contains(&Rank::Three)
-        && ranks.contains(&Rank::Four)
-    {
-        return true;
-    }
-
-    // Check for 4 consecutive ranks in any window
-    for window in ranks.windows(4) {
-        let mut is_consecutive = true;
-
-        for i in 0..3 {
-            if let Some(expected_next) = window[i].next() {
-                if expected_next != window[i + 1] {
-                    is_consecutive = false;
-                    break;
-                }
+impl fmt::Display for HandEvaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |cards: &[Card]| {
+            if cards.is_empty() {
+                "none".to_string()
             } else {
-                // Current rank doesn't have a next rank
-                is_consecutive = false;
-                break;
+                cards.iter().join(", ")
             }
-        }
+        };
 
-        if is_consecutive {
-            return true;
+        writeln!(f, "{}", self.hand)?;
+        writeln!(f, "Scoring: {}", list(&self.scoring_cards))?;
+        writeln!(f, "Kickers: {}", list(&self.kickers))?;
+        if !self.modifiers.is_empty() {
+            writeln!(f, "Needs: {}", self.modifiers.iter().join(", "))?;
         }
+        if !self.suit_assignments.is_empty() {
+            let counted = self
+                .suit_assignments
+                .iter()
+                .map(|assignment| format!("{} as {}", assignment.card, assignment.suit))
+                .join(", ");
+            writeln!(f, "Counted: {}", counted)?;
+        }
+        Ok(())
     }
+}
 
-    false // No 4-card straight found
+/// One part of a hand (e.g. the Flush part of a Straight Flush)
+struct HandPart {
+    /// Positions of the cards forming the part, in played order
+    positions: Vec<usize>,
+    /// Rule-bending jokers needed to form the part
+    modifiers: Vec<Joker>,
+    /// Cards the part counts as a suit other than their own
+    suit_assignments: Vec<SuitAssignment>,
 }
 
-/// Checks if the cards form a straight with the Shortcut joker active
-/// Shortcut allows straights to be made with gaps of 1 rank
-fn has_shortcut_straight(cards: &[Card]) -> bool {
-    // Extract ranks, sort them, and remove duplicates to handle the case properly
-    let mut ranks: Vec<Rank> = cards.iter().map(|card| card.rank).collect();
-    ranks.sort();
-    ranks.dedup();
-
-    // Need at least 5 unique ranks
-    if ranks.len() < 5 {
-        return false;
-    }
+/// Returns which rule-bending jokers are present as
+/// (Four Fingers, Shortcut, Smeared Joker)
+pub fn active_rules(jokers: &[JokerCard]) -> (bool, bool, bool) {
+    let has_joker = |joker: Joker| jokers.iter().any(|j| j.joker == joker);
+    (
+        has_joker(Joker::FourFingers),
+        has_joker(Joker::Shortcut),
+        has_joker(Joker::SmearedJoker),
+    )
+}
 
-    // Convert ranks to their numeric values for easier gap calculation
-    let rank_values: Vec<i32> = ranks.iter().map(|r| r.rank_value() as i32).collect();
-
-    // Check all possible windows of 5 cards for valid shortcut straights
-    'window_loop: for window_start in 0..=rank_values.len() - 5 {
-        let window = &rank_values[window_start..window_start + 5];
-
-        // For a 5-card shortcut straight, the total span should be at most 8
-        // (4 positions with up to 4 gaps of 1 rank each)
-        let total_span = window[4] - window[0];
-
-        if total_span <= 8 {
-            // Count individual gaps to ensure no gap is larger than 1
-            for i in 0..4 {
-                let gap = window[i + 1] - window[i] - 1;
-                if gap > 1 {
-                    // Gap too large, try next window
-                    continue 'window_loop;
-                }
-            }
-            return true;
+/// Returns the sizes to try for a Flush or Straight, with and without the
+/// optional rule modifier, from the fewest modifiers to the most
+fn attempts(four_fingers_active: bool, modifier_active: bool) -> Vec<(usize, bool)> {
+    let mut attempts = vec![(FULL_HAND_SIZE, false)];
+    if modifier_active {
+        attempts.push((FULL_HAND_SIZE, true));
+    }
+    if four_fingers_active {
+        attempts.push((FOUR_FINGERS_HAND_SIZE, false));
+        if modifier_active {
+            attempts.push((FOUR_FINGERS_HAND_SIZE, true));
         }
     }
+    attempts
+}
 
-    // Special handling for Ace-low straights
-    if ranks.contains(&Rank::Ace) {
-        // Create a new array with Ace = 1 (low) instead of 14 (high)
-        let mut low_ace_values: Vec<i32> = Vec::new();
-        low_ace_values.push(1); // Add 1 (low Ace)
+/// Returns true if a card counts as the given suit
+pub fn counts_as_suit(card: &Card, suit: Suit, smeared: bool) -> bool {
+    card.enhancement == Some(Enhancement::Wild)
+        || card.suit == suit
+        || (smeared && card.suit.other_suit_of_same_color() == suit)
+}
 
-        // Add all non-Ace ranks
-        for rank in &ranks {
-            if *rank != Rank::Ace {
-                low_ace_values.push(rank.rank_value() as i32);
+/// Finds the cards forming a Flush (all cards of the same suit)
+///
+/// If several suits form a Flush, the suit with the most cards is used, and
+/// then the one that needs the fewest cards counted as another suit.
+fn find_flush(cards: &[Card], four_fingers_active: bool, smeared_active: bool) -> Option<HandPart> {
+    for (size, smeared) in attempts(four_fingers_active, smeared_active) {
+        let suits: Vec<(Suit, Vec<usize>)> = all::<Suit>()
+            .map(|suit| {
+                let positions = (0..cards.len())
+                    .filter(|&i| counts_as_suit(&cards[i], suit, smeared))
+                    .collect();
+                (suit, positions)
+            })
+            .collect();
+        // The most cards, then the fewest counted as another suit; `max_by_key`
+        // keeps the last maximum, so search from the back to keep the first
+        let best_suit = suits.into_iter().rev().max_by_key(|(suit, positions)| {
+            let own_suit = positions.iter().filter(|&&i| cards[i].suit == *suit);
+            (positions.len(), own_suit.count())
+        });
+
+        if let Some((suit, positions)) = best_suit.filter(|(_, positions)| positions.len() >= size)
+        {
+            let mut modifiers = Vec::new();
+            if size == FOUR_FINGERS_HAND_SIZE {
+                modifiers.push(Joker::FourFingers);
             }
-        }
-        low_ace_values.sort();
-
-        // Check for valid Ace-low shortcut straights
-        'ace_window_loop: for window_start in 0..=low_ace_values.len() - 5 {
-            let window = &low_ace_values[window_start..window_start + 5];
-
-            let total_span = window[4] - window[0];
-
-            if total_span <= 8 {
-                for i in 0..4 {
-                    let gap = window[i + 1] - window[i] - 1;
-                    if gap > 1 {
-                        continue 'ace_window_loop;
-                    }
-                }
-                return true;
+            if smeared {
+                modifiers.push(Joker::SmearedJoker);
             }
+            let suit_assignments = positions
+                .iter()
+                .filter(|&&i| cards[i].suit != suit)
+                .map(|&i| SuitAssignment {
+                    card: cards[i],
+                    suit,
+                })
+                .collect();
+            return Some(HandPart {
+                positions,
+                modifiers,
+                suit_assignments,
+            });
         }
     }
-
-    false
+    None
 }
 
-/// Checks if the cards form a 4-card straight with gaps (for Four Fingers + Shortcut)
-fn has_four_card_shortcut_straight(cards: &[Card]) -> bool {
-    if cards.len() < 4 {
-        return false;
-    }
-
-    let mut ranks: Vec<Rank> = cards.iter().map(|card| card.rank).collect();
-    ranks.sort();
-    ranks.dedup();
+/// Assigns a different card to each of the four suits, if the cards can cover them all
+///
+/// Cards are kept to their own suit wherever possible, so only the cards that
+/// had to be counted as another suit are returned.
+pub fn cover_every_suit(cards: &[Card], smeared: bool) -> Option<Vec<SuitAssignment>> {
+    let suits: Vec<Suit> = all::<Suit>().collect();
+    (0..cards.len())
+        .permutations(suits.len())
+        .filter(|chosen| {
+            chosen
+                .iter()
+                .zip(&suits)
+                .all(|(&i, &suit)| counts_as_suit(&cards[i], suit, smeared))
+        })
+        // `min_by_key` keeps the first minimum
+        .min_by_key(|chosen| {
+            chosen
+                .iter()
+                .zip(&suits)
+                .filter(|&(&i, &suit)| cards[i].suit != suit)
+                .count()
+        })
+        .map(|chosen| {
+            chosen
+                .into_iter()
+                .zip(suits.iter().copied())
+                .filter(|&(i, suit)| cards[i].suit != suit)
+                .sorted()
+                .map(|(i, suit)| SuitAssignment {
+                    card: cards[i],
+                    suit,
+                })
+                .collect()
+        })
+}
 
-    // Need at least 4 unique ranks
-    if ranks.len() < 4 {
-        return false;
+/// Returns the rank at a straight position, from 1 (low Ace) to 14 (high Ace)
+fn rank_at(position: usize) -> Rank {
+    if position == 1 {
+        Rank::Ace
+    } else {
+        all::<Rank>().nth(position - 2).unwrap_or(Rank::Ace)
     }
+}
 
-    // Convert ranks to numeric values
-    let rank_values: Vec<i32> = ranks.iter().map(|r| r.rank_value() as i32).collect();
-
-    // Check all possible windows of 4 cards
-    'window_loop: for window_start in 0..=rank_values.len() - 4 {
-        let window = &rank_values[window_start..window_start + 4];
-
-        // For a 4-card shortcut straight, the total span should be at most 6
-        // (3 positions with up to 3 gaps of 1 rank each)
-        let total_span = window[3] - window[0];
-
-        if total_span <= 6 {
-            // Check individual gaps to ensure no gap is larger than 1
-            for i in 0..3 {
-                let gap = window[i + 1] - window[i] - 1;
-                if gap > 1 {
-                    // Gap too large, try next window
-                    continue 'window_loop;
-                }
-            }
-            return true;
+/// Finds the ranks of the first run of at least `length` ranks
+///
+/// Runs are extended as far as they go, and with `allow_gaps` a single
+/// missing rank between two present ranks does not break the run.
+fn straight_ranks(cards: &[Card], length: usize, allow_gaps: bool) -> Option<Vec<Rank>> {
+    let mut run: Vec<Rank> = Vec::new();
+    let mut skipped = false;
+
+    for position in 1..=14 {
+        let rank = rank_at(position);
+        if cards.iter().any(|card| card.rank == rank) {
+            run.push(rank);
+            skipped = false;
+        } else if allow_gaps && !skipped && !run.is_empty() {
+            skipped = true;
+        } else if run.len() >= length {
+            return Some(run);
+        } else {
+            run.clear();
+            skipped = false;
         }
     }
 
-    // Special case for Ace-low
-    if ranks.contains(&Rank::Ace) {
-        let mut low_ace_values = vec![1]; // Ace as 1
+    (run.len() >= length).then_some(run)
+}
 
-        // Add non-Ace ranks
-        for rank in &ranks {
-            if *rank != Rank::Ace {
-                low_ace_values.push(rank.rank_value() as i32);
-            }
+/// Finds the cards forming a Straight (consecutive ranks)
+///
+/// Every card with a rank in the run is part of the Straight.
+fn find_straight(
+    cards: &[Card],
+    four_fingers_active: bool,
+    shortcut_active: bool,
+) -> Option<HandPart> {
+    for (size, gaps) in attempts(four_fingers_active, shortcut_active) {
+        if cards.len() < size {
+            continue;
         }
-        low_ace_values.sort();
-
-        // Check Ace-low windows
-        'ace_window_loop: for window_start in 0..=low_ace_values.len() - 4 {
-            if low_ace_values.len() < window_start + 4 {
-                break;
+        if let Some(ranks) = straight_ranks(cards, size, gaps) {
+            let mut modifiers = Vec::new();
+            if size == FOUR_FINGERS_HAND_SIZE {
+                modifiers.push(Joker::FourFingers);
             }
-
-            let window = &low_ace_values[window_start..window_start + 4];
-            let total_span = window[3] - window[0];
-
-            if total_span <= 6 {
-                // Check individual gaps
-                for i in 0..3 {
-                    let gap = window[i + 1] - window[i] - 1;
-                    if gap > 1 {
-                        continue 'ace_window_loop;
-                    }
-                }
-                return true;
+            if gaps {
+                modifiers.push(Joker::Shortcut);
             }
+            return Some(HandPart {
+                positions: (0..cards.len())
+                    .filter(|&i| ranks.contains(&cards[i].rank))
+                    .collect(),
+                modifiers,
+                suit_assignments: Vec::new(),
+            });
         }
     }
-
-    false
+    None
 }
 
-/// Identifies the poker hand type from a set of cards
+/// Evaluates the best poker hand formed by the played cards
 ///
-/// This function analyses the cards and determines the poker hand type
-/// based on the rules of Balatro. It supports standard poker hands
-/// as well as special cases like shortcut straights and four-card straights.
-pub fn identify_hand(
+/// This function analyses the cards based on the rules of Balatro, taking the
+/// active rule modifiers into account, and returns the hand with its breakdown.
+pub fn evaluate_hand(
     cards: &[Card],
     four_fingers_active: bool,
     shortcut_active: bool,
     smeared_joker_active: bool,
-) -> GameResult<PokerHand> {
-    if cards.len() < 2 {
-        // With only 0 or 1 card, it's always a High Card
-        return Ok(PokerHand::HighCard);
-    }
+) -> HandEvaluation {
+    // Positions of the cards of each rank, in order of first appearance
+    let mut rank_groups: IndexMap<Rank, Vec<usize>> = IndexMap::new();
+    for (i, card) in cards.iter().enumerate() {
+        rank_groups.entry(card.rank).or_default().push(i);
+    }
+    let group_of = |size: usize| rank_groups.values().find(|group| group.len() == size);
+    let groups_of_at_least = |size: usize| {
+        rank_groups
+            .values()
+            .filter(|group| group.len() >= size)
+            .count()
+    };
 
-    let rank_count = group_rank(cards);
-    let all_same_rank = rank_count.len() == 1 && cards.len() >= 5;
-    let has_flush = is_flush(cards, smeared_joker_active);
-    let has_straight = is_straight(cards) || (shortcut_active && has_shortcut_straight(cards));
-    let has_three_two = has_three_two_pattern(cards);
-    let has_four_of_a_kind = rank_count.values().any(|&count| count >= 4);
-    let has_three_of_a_kind = rank_count.values().any(|&count| count >= 3);
-
-    // Count actual pairs
-    let pair_count = rank_count.values().filter(|&&count| count == 2).count();
-    // Special case for exactly 2 cards of the same rank
-    let is_simple_pair = cards.len() == 2 && rank_count.len() == 1;
-
-    // Four Fingers joker support
-    let has_four_card_flush = if four_fingers_active && cards.len() >= 4 {
-        has_four_card_flush(cards, smeared_joker_active)
-    } else {
-        false
+    let flush = find_flush(cards, four_fingers_active, smeared_joker_active);
+    let straight = find_straight(cards, four_fingers_active, shortcut_active);
+    let contains_flush = flush.is_some();
+    let contains_straight = straight.is_some();
+
+    let all_same_rank = rank_groups.len() == 1 && cards.len() >= FULL_HAND_SIZE;
+    let three_two = rank_groups.len() == 2 && group_of(3).is_some() && group_of(2).is_some();
+    let pairs: Vec<usize> = rank_groups
+        .values()
+        .filter(|group| group.len() == 2)
+        .flatten()
+        .copied()
+        .collect();
+    let every_card = || HandPart {
+        positions: (0..cards.len()).collect(),
+        modifiers: Vec::new(),
+        suit_assignments: Vec::new(),
+    };
+    let group = |positions: &[usize]| HandPart {
+        positions: positions.to_vec(),
+        modifiers: Vec::new(),
+        suit_assignments: Vec::new(),
     };
 
-    let has_four_card_straight = if four_fingers_active && cards.len() >= 4 {
-        has_four_card_straight(cards) || (shortcut_active && has_four_card_shortcut_straight(cards))
+    let (hand, part) = if all_same_rank && let Some(flush) = &flush {
+        (
+            PokerHand::FlushFive,
+            HandPart {
+                modifiers: flush.modifiers.clone(),
+                suit_assignments: flush.suit_assignments.clone(),
+                ..every_card()
+            },
+        )
+    } else if three_two && let Some(flush) = &flush {
+        (
+            PokerHand::FlushHouse,
+            HandPart {
+                modifiers: flush.modifiers.clone(),
+                suit_assignments: flush.suit_assignments.clone(),
+                ..every_card()
+            },
+        )
+    } else if all_same_rank {
+        (PokerHand::FiveOfAKind, every_card())
+    } else if let (Some(flush), Some(straight)) = (&flush, &straight) {
+        // Both parts score, with the modifiers either of them needed
+        let positions = flush
+            .positions
+            .iter()
+            .chain(&straight.positions)
+            .copied()
+            .sorted()
+            .dedup()
+            .collect();
+        let modifiers = all::<Joker>()
+            .filter(|joker| flush.modifiers.contains(joker) || straight.modifiers.contains(joker))
+            .collect();
+        (
+            PokerHand::StraightFlush,
+            HandPart {
+                positions,
+                modifiers,
+                suit_assignments: flush.suit_assignments.clone(),
+            },
+        )
+    } else if let Some(four) = group_of(4) {
+        (PokerHand::FourOfAKind, group(four))
+    } else if three_two {
+        (PokerHand::FullHouse, every_card())
+    } else if let Some(flush) = flush {
+        (PokerHand::Flush, flush)
+    } else if let Some(straight) = straight {
+        (PokerHand::Straight, straight)
+    } else if let Some(three) = group_of(3) {
+        (PokerHand::ThreeOfAKind, group(three))
+    } else if pairs.len() >= 4 {
+        (
+            PokerHand::TwoPair,
+            group(&pairs.iter().copied().sorted().collect::<Vec<_>>()),
+        )
+    } else if pairs.len() == 2 {
+        (PokerHand::Pair, group(&pairs))
     } else {
-        false
+        // Only the first card of the highest rank scores
+        let highest = (0..cards.len()).rev().max_by_key(|&i| cards[i].rank);
+        (
+            PokerHand::HighCard,
+            group(&highest.into_iter().collect::<Vec<_>>()),
+        )
     };
 
-    // Effective conditions
-    let effective_flush = has_flush || has_four_card_flush;
-    let effective_straight = has_straight || has_four_card_straight;
-
-    // 12. Flush Five (all same rank and suit)
-    if all_same_rank && effective_flush {
-        return Ok(PokerHand::FlushFive);
-    }
-
-    // 11. Flush House (full house with all same suit)
-    if has_three_two && effective_flush {
-        return Ok(PokerHand::FlushHouse);
-    }
-
-    // 10. Five of a Kind (all same rank)
-    if all_same_rank {
-        return Ok(PokerHand::FiveOfAKind);
-    }
-
-    // 9. Straight Flush
-    if effective_straight && effective_flush {
-        return Ok(PokerHand::StraightFlush);
-    }
-
-    // 8. Four of a Kind
-    if has_four_of_a_kind {
-        return Ok(PokerHand::FourOfAKind);
-    }
-
-    // 7. Full House
-    if has_three_two {
-        return Ok(PokerHand::FullHouse);
-    }
-
-    // 6. Flush
-    if effective_flush {
-        return Ok(PokerHand::Flush);
-    }
-
-    // 5. Straight
-    if effective_straight {
-        return Ok(PokerHand::Straight);
-    }
-
-    // 4. Three of a Kind
-    if has_three_of_a_kind {
-        return Ok(PokerHand::ThreeOfAKind);
-    }
-
-    // 3. Two Pair
-    if pair_count >= 2 {
-        return Ok(PokerHand::TwoPair);
-    }
-
-    // 2. Pair - a single pair or exactly two cards of the same rank
-    if pair_count == 1 || is_simple_pair {
-        return Ok(PokerHand::Pair);
-    }
-
-    // 1. High Card
-    Ok(PokerHand::HighCard)
-}
-
-/// Helper function to find the cards that form a shortcut straight
-fn find_shortcut_straight_cards(cards: &[Card]) -> Vec<Card> {
-    // Extract ranks with their original card indices
-    let mut rank_info: Vec<(usize, Rank, i32)> = cards
-        .iter()
-        .enumerate()
-        .map(|(i, card)| (i, card.rank, card.rank.rank_value() as i32))
+    let scoring_cards = part.positions.iter().map(|&i| cards[i]).collect();
+    let kickers = (0..cards.len())
+        .filter(|i| !part.positions.contains(i))
+        .map(|i| cards[i])
         .collect();
+    let conditions = HandConditions {
+        contains_pair: groups_of_at_least(2) >= 1,
+        contains_two_pair: groups_of_at_least(2) >= 2,
+        contains_three_of_a_kind: groups_of_at_least(3) >= 1,
+        contains_straight,
+        contains_flush,
+    };
 
-    rank_info.sort_by_key(|&(_, _, val)| val);
-
-    // Get unique ranks (maintain original indices)
-    let mut unique_rank_info = Vec::new();
-    let mut last_rank = None;
-
-    for &(idx, rank, value) in &rank_info {
-        if last_rank != Some(rank) {
-            unique_rank_info.push((idx, rank, value));
-            last_rank = Some(rank);
-        }
-    }
-
-    // Check standard straights with gaps
-    if unique_rank_info.len() >= 5 {
-        for window_start in 0..=unique_rank_info.len() - 5 {
-            let window = &unique_rank_info[window_start..window_start + 5];
-            let total_gap = window[4].2 - window[0].2 - 4; // Expected difference is 4
-
-            if total_gap <= 1 {
-                // Valid shortcut straight
-                return window.iter().map(|&(i, _, _)| cards[i]).collect();
-            }
-        }
-    }
-
-    // Check for Ace-low straights with gaps
-    if unique_rank_info
-        .iter()
-        .any(|&(_, rank, _)| rank == Rank::Ace)
-    {
-        // Find the Ace's index
-        let ace_idx = unique_rank_info
-            .iter()
-            .position(|&(_, rank, _)| rank == Rank::Ace)
-            .unwrap();
-        let (orig_ace_idx, _, _) = unique_rank_info[ace_idx];
-
-        // Create a new array with Ace = 1 (low) instead of 14 (high)
-        let mut low_ace_info = vec![(orig_ace_idx, Rank::Ace, 1)];
-
-        // Add all non-Ace ranks
-        for &(idx, rank, value) in &unique_rank_info {
-            if rank != Rank::Ace {
-                low_ace_info.push((idx, rank, value));
-            }
-        }
-
-        // Sort by rank value
-        low_ace_info.sort_by_key(|&(_, _, val)| val);
-
-        // Check for straights with the Ace as low
-        if low_ace_info.len() >= 5 {
-            for window_start in 0..=low_ace_info.len() - 5 {
-                let window = &low_ace_info[window_start..window_start + 5];
-                let total_gap = window[4].2 - window[0].2 - 4;
-
-                if total_gap <= 1 {
-                    // Valid straight with at most one gap
-                    return window.iter().map(|&(i, _, _)| cards[i]).collect();
-                }
-            }
-        }
+    HandEvaluation {
+        hand,
+        scoring_cards,
+        kickers,
+        modifiers: part.modifiers,
+        conditions,
+        suit_assignments: part.suit_assignments,
     }
-
-    // Fallback
-    cards.iter().take(5).cloned().collect()
 }
 
-/// Helper function to find the cards that form a 4-card shortcut straight
-fn find_four_card_shortcut_straight(cards: &[Card]) -> Vec<Card> {
-    let mut rank_indices: Vec<(usize, u8)> = cards
-        .iter()
-        .enumerate()
-        .map(|(i, card)| (i, card.rank.rank_value() as u8))
-        .collect();
-
-    rank_indices.sort_by_key(|&(_, rank)| rank);
-
-    // Get unique ranks (maintain original indices)
-    let mut unique_ranks = Vec::new();
-    let mut last_rank = 0;
-    for &(idx, rank) in &rank_indices {
-        if rank != last_rank {
-            unique_ranks.push((idx, rank));
-            last_rank = rank;
-        }
-    }
-
-    // Special case: check for Ace-low shortcut straight
-    if unique_ranks.iter().any(|&(_, r)| r == 14) {
-        // Ace present
-        let mut low_ranks = Vec::new();
-        let mut ace_idx = 0;
-
-        for &(idx, rank) in &unique_ranks {
-            if rank == 14 {
-                ace_idx = idx;
-            } else if rank <= 5 {
-                // Consider ranks 2-5 for Ace-low with a gap
-                low_ranks.push((idx, rank));
-            }
-        }
+/// Straight lookup flag: 5 consecutive ranks
+const STRAIGHT: u8 = 1;
+/// Straight lookup flag: 5 ranks with gaps of 1 rank (Shortcut)
+const SHORTCUT_STRAIGHT: u8 = 1 << 1;
+/// Straight lookup flag: 4 consecutive ranks (Four Fingers)
+const FOUR_CARD_STRAIGHT: u8 = 1 << 2;
+/// Straight lookup flag: 4 ranks with gaps of 1 rank (Four Fingers and Shortcut)
+const FOUR_CARD_SHORTCUT_STRAIGHT: u8 = 1 << 3;
 
-        // Check for Ace-low shortcut straight (A-2-4-5 or similar)
-        if low_ranks.len() >= 3 {
-            // Sort low ranks
-            low_ranks.sort_by_key(|&(_, r)| r);
-
-            // Check if we can form a valid shortcut sequence
-            for start in 0..=low_ranks.len() - 3 {
-                let window = &low_ranks[start..start + 3];
-                let total_span = window[2].1 - window[0].1;
-
-                // For a shortcut straight with 3 cards, the span should be at most 4
-                if total_span <= 4 {
-                    // We found a valid sequence including Ace as low
-                    let mut result = Vec::new();
-                    result.push(cards[ace_idx]); // Add Ace
-
-                    // Add the three cards from the window
-                    for &(i, _) in window {
-                        result.push(cards[i]);
-                    }
-
-                    // Return all 4 cards
-                    return result;
-                }
-            }
-        }
-    }
+/// Number of ranks, and so of bits in a rank mask (bit 0 is Two, bit 12 is Ace)
+const RANK_COUNT: usize = 13;
 
-    // Check for regular shortcut straight
-    for start in 0..=unique_ranks.len() - 4 {
-        let window = &unique_ranks[start..start + 4];
-        let total_span = window[3].1 - window[0].1;
-
-        // For a shortcut straight with 4 cards, the span should be at most 5
-        if total_span <= 5 {
-            // Check if there's at most one gap
-            let mut gap_count = 0;
-            for i in 0..3 {
-                let gap = window[i + 1].1 - window[i].1 - 1;
-                match gap {
-                    0 => (), // No gap
-                    1 => gap_count += 1,
-                    _ => {
-                        gap_count = 2; // Too big gap, invalid
-                        break;
-                    }
-                }
-            }
+/// The straights each set of ranks can form, indexed by rank mask
+static STRAIGHT_TABLE: [u8; 1 << RANK_COUNT] = build_straight_table();
 
-            if gap_count <= 1 {
-                // Valid 4-card shortcut straight
-                return window.iter().map(|&(i, _)| cards[i]).collect();
+/// Returns the length of the longest run of ranks in a rank mask
+///
+/// Follows the same rules as `straight_ranks`, with the Ace both low and high.
+const fn longest_run(rank_mask: usize, allow_gaps: bool) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut skipped = false;
+
+    let mut position = 1;
+    while position <= 14 {
+        let bit = if position == 1 {
+            RANK_COUNT - 1
+        } else {
+            position - 2
+        };
+        if rank_mask & (1 << bit) != 0 {
+            run += 1;
+            skipped = false;
+            if run > longest {
+                longest = run;
             }
+        } else if allow_gaps && !skipped && run > 0 {
+            skipped = true;
+        } else {
+            run = 0;
+            skipped = false;
         }
+        position += 1;
     }
-
-    // Fallback
-    cards.iter().take(4).copied().collect()
+    longest
 }
 
-/// Find cards that make a standard 4-card straight (without gaps)
-fn find_four_card_straight(cards: &[Card]) -> Vec<Card> {
-    let mut rank_indices: Vec<(usize, u8)> = cards
-        .iter()
-        .enumerate()
-        .map(|(i, card)| (i, card.rank.rank_value() as u8))
-        .collect();
-
-    rank_indices.sort_by_key(|&(_, rank)| rank);
-
-    // Check for consecutive sequences of 4 cards
-    for window in rank_indices.windows(4) {
-        if window[3].1 - window[0].1 == 3 {
-            return window.iter().map(|&(i, _)| cards[i]).collect();
+/// Precomputes the straight flags of every rank mask at compile time
+const fn build_straight_table() -> [u8; 1 << RANK_COUNT] {
+    let mut table = [0; 1 << RANK_COUNT];
+    let mut rank_mask = 0;
+    while rank_mask < table.len() {
+        let run = longest_run(rank_mask, false);
+        let gapped_run = longest_run(rank_mask, true);
+        let mut flags = 0;
+        if run >= FULL_HAND_SIZE {
+            flags |= STRAIGHT;
         }
-    }
-
-    // Check for A-2-3-4 straight
-    let ace_indices: Vec<usize> = rank_indices
-        .iter()
-        .filter(|&&(_, rank)| rank == 14) // Ace
-        .map(|&(i, _)| i)
-        .collect();
-
-    let low_cards: Vec<(usize, u8)> = rank_indices
-        .iter()
-        .filter(|&&(_, rank)| (2..=4).contains(&rank))
-        .copied()
-        .collect();
-
-    if !ace_indices.is_empty()
-        && low_cards.len() >= 3
-        && low_cards.iter().any(|&(_, r)| r == 2)
-        && low_cards.iter().any(|&(_, r)| r == 3)
-        && low_cards.iter().any(|&(_, r)| r == 4)
-    {
-        let mut result = Vec::new();
-        // Add the Ace
-        result.push(cards[ace_indices[0]]);
-        // Add the 2, 3, 4
-        for &(i, r) in &low_cards {
-            if (2..=4).contains(&r) && result.len() < 4 {
-                result.push(cards[i]);
-            }
+        if gapped_run >= FULL_HAND_SIZE {
+            flags |= SHORTCUT_STRAIGHT;
         }
-        return result;
-    }
-
-    // Fallback
-    cards.iter().take(4).copied().collect()
-}
-
-/// Find cards forming a four-card flush
-fn find_four_card_flush(cards: &[Card], smeared_joker_active: bool) -> Vec<Card> {
-    // Group by suit
-    let suit_groups = group_by_suit(cards, smeared_joker_active);
-
-    // Find the first suit with at least 4 cards
-    for (_, suit_cards) in suit_groups {
-        if suit_cards.len() >= 4 {
-            // Take the first 4 cards of that suit
-            return suit_cards.iter().take(4).map(|&&card| card).collect();
+        if run >= FOUR_FINGERS_HAND_SIZE {
+            flags |= FOUR_CARD_STRAIGHT;
         }
-    }
-
-    // Fallback
-    Vec::new()
-}
-
-/// Find cards forming a shortcut straight flush (5 cards with at most one gap)
-fn find_shortcut_straight_flush_cards(cards: &[Card], smeared_joker_active: bool) -> Vec<Card> {
-    // Group by suit
-    let suit_groups = group_by_suit(cards, smeared_joker_active);
-
-    // Check each suit group for a shortcut straight
-    for (_, suit_cards) in suit_groups {
-        if suit_cards.len() >= 5 {
-            let suit_cards_vec: Vec<Card> = suit_cards.iter().map(|&&c| c).collect();
-            if has_shortcut_straight(&suit_cards_vec) {
-                return find_shortcut_straight_cards(&suit_cards_vec);
-            }
+        if gapped_run >= FOUR_FINGERS_HAND_SIZE {
+            flags |= FOUR_CARD_SHORTCUT_STRAIGHT;
         }
+        table[rank_mask] = flags;
+        rank_mask += 1;
     }
-
-    // Fallback
-    Vec::new()
+    table
 }
 
-/// Returns the cards that contribute to the scoring for a given poker hand
+/// Identifies the poker hand type from a set of cards
 ///
-/// According to the rules, generally only the cards relevant to the poker hand
-/// are scored, and all others are unscored. This function identifies which cards
-/// should be scored based on the poker hand type.
-pub fn get_scoring_cards(
-    hand_type: &PokerHand,
+/// This gives the same hand as `evaluate_hand`, but only counts ranks and suits
+/// into fixed-size arrays and looks straights up in a precomputed table, so it
+/// never allocates. Use it wherever hands are identified in bulk.
+pub fn identify_hand(
     cards: &[Card],
     four_fingers_active: bool,
     shortcut_active: bool,
     smeared_joker_active: bool,
-) -> Vec<Card> {
-    match hand_type {
-        PokerHand::HighCard => {
-            // For high card, only the highest card scores
-            let rank_map: IndexMap<Rank, Vec<&Card>> = group_by_rank(cards);
-            let mut ranks: Vec<Rank> = rank_map.keys().copied().collect();
-            ranks.sort_by(|a: &Rank, b: &Rank| b.cmp(a)); // Sort in descending order
-
-            // Get the highest rank's cards
-            if let Some(highest_rank) = ranks.first() {
-                if let Some(cards) = rank_map.get(highest_rank) {
-                    if !cards.is_empty() {
-                        return vec![*cards[0]]; // Return only the first card of the highest rank
-                    }
-                }
-            }
-            vec![]
-        }
-        PokerHand::Pair => {
-            // Find the pair
-            group_by_rank(cards)
-                .into_iter()
-                .find_map(|(_, cards)| {
-                    if cards.len() == 2 {
-                        Some(cards.iter().map(|&card| *card).collect())
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or_default()
-        }
-        PokerHand::TwoPair => {
-            group_by_rank(cards)
-                .into_iter()
-                .filter_map(|(_, cards)| {
-                    if cards.len() == 2 {
-                        // This is a pair
-                        Some(cards.iter().map(|&card| *card).collect::<Vec<Card>>())
-                    } else {
-                        None
-                    }
-                })
-                .flatten() // Flatten the Vec<Vec<Card>> into Vec<Card>
-                .collect()
-        }
-        PokerHand::ThreeOfAKind => {
-            // Find three of a kind
-            group_by_rank(cards)
-                .into_iter()
-                .find_map(|(_, cards)| {
-                    if cards.len() == 3 {
-                        Some(cards.iter().map(|&card| *card).collect())
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or_default()
-        }
-        PokerHand::FourOfAKind => {
-            // Find four of a kind
-            group_by_rank(cards)
-                .into_iter()
-                .find_map(|(_, cards)| {
-                    if cards.len() == 4 {
-                        Some(cards.iter().map(|&card| *card).collect())
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or_default()
-        }
-        PokerHand::Straight => {
-            if shortcut_active {
-                if is_straight(cards) {
-                    // Regular 5-card straight
-                    cards.to_vec()
-                } else if has_shortcut_straight(cards) {
-                    // Find the 5 cards that form a shortcut straight
-                    find_shortcut_straight_cards(cards)
-                } else if four_fingers_active && has_four_card_shortcut_straight(cards) {
-                    // Find the 4 cards that form a shortcut straight with Four Fingers
-                    find_four_card_shortcut_straight(cards)
-                } else if four_fingers_active && has_four_card_straight(cards) {
-                    // Standard 4-card straight with Four Fingers
-                    find_four_card_straight(cards)
-                } else {
-                    // Fallback
-                    cards.to_vec()
-                }
-            } else if four_fingers_active && !is_straight(cards) && has_four_card_straight(cards) {
-                // Find the 4 cards that form a straight
-                find_four_card_straight(cards)
-            } else {
-                // Regular 5-card straight
-                cards.to_vec()
-            }
-        }
-        PokerHand::Flush => {
-            if four_fingers_active
-                && !is_flush(cards, smeared_joker_active)
-                && has_four_card_flush(cards, smeared_joker_active)
-            {
-                // Find the suit with at least 4 cards
-                let suit_groups = group_by_suit(cards, smeared_joker_active);
-                if let Some((_, suit_cards)) = suit_groups
-                    .iter()
-                    .find(|(_, suit_cards)| suit_cards.len() >= 4 && suit_cards.len() < 5)
-                {
-                    // Take the first 4 cards of that suit
-                    return suit_cards.iter().take(4).map(|&&card| card).collect();
-                }
-                vec![]
-            } else {
-                // Regular 5-card flush
-                cards.to_vec()
-            }
+) -> GameResult<PokerHand> {
+    let mut rank_counts = [0u8; RANK_COUNT];
+    let mut suit_counts = [0u8; 4];
+    let mut wild_cards = 0;
+    let mut rank_mask = 0;
+    for card in cards {
+        rank_counts[card.rank as usize] += 1;
+        rank_mask |= 1 << card.rank as usize;
+        if card.enhancement == Some(Enhancement::Wild) {
+            wild_cards += 1;
+        } else {
+            suit_counts[card.suit as usize] += 1;
         }
-        PokerHand::StraightFlush => {
-            if four_fingers_active {
-                // 1. Identify the flush component (5-card or 4-card)
-                let flush_cards = if is_flush(cards, smeared_joker_active) {
-                    // Regular 5-card flush - all cards of the same suit
-                    cards
-                        .iter()
-                        .filter(|card| {
-                            let dominant_suit = cards
-                                .iter()
-                                .filter(|c| c.enhancement != Some(ortalib::Enhancement::Wild))
-                                .fold(std::collections::HashMap::new(), |mut map, c| {
-                                    *map.entry(c.suit).or_insert(0) += 1;
-                                    map
-                                })
-                                .into_iter()
-                                .max_by_key(|(_, count)| *count)
-                                .map(|(suit, _)| suit)
-                                .unwrap_or(card.suit);
-
-                            card.enhancement == Some(ortalib::Enhancement::Wild)
-                                || card.suit == dominant_suit
-                        })
-                        .copied()
-                        .collect::<Vec<Card>>()
-                } else if has_four_card_flush(cards, smeared_joker_active) {
-                    // 4-card flush
-                    find_four_card_flush(cards, smeared_joker_active)
-                } else {
-                    Vec::new() // No flush component
-                };
-
-                // 2. Identify the straight component
-                let straight_cards = if is_straight(cards) {
-                    // Regular 5-card straight
-                    cards.to_vec()
-                } else if shortcut_active && has_shortcut_straight(cards) {
-                    // 5-card straight with gaps
-                    find_shortcut_straight_cards(cards)
-                } else if has_four_card_straight(cards) {
-                    // 4-card straight
-                    find_four_card_straight(cards)
-                } else if shortcut_active && has_four_card_shortcut_straight(cards) {
-                    // 4-card straight with gaps
-                    find_four_card_shortcut_straight(cards)
-                } else {
-                    Vec::new() // No straight component
-                };
-
-                // 3. Union flush and straight components to get all relevant cards
-                let mut combined_cards = flush_cards;
-                for card in straight_cards {
-                    if !combined_cards.contains(&card) {
-                        combined_cards.push(card);
-                    }
-                }
-
-                // 4. Return the combined cards
-                if !combined_cards.is_empty() {
-                    return combined_cards;
-                }
-
-                // Fallback to all cards
-                return cards.to_vec();
-            }
-
-            // Without Four Fingers active
-            if shortcut_active
-                && find_shortcut_straight_flush_cards(cards, smeared_joker_active).len() == 5
-            {
-                return find_shortcut_straight_flush_cards(cards, smeared_joker_active);
-            }
-
-            // Fallback
-            cards.to_vec()
-        } // For these hands, all cards are scored
-        PokerHand::FiveOfAKind
-        | PokerHand::FlushHouse
-        | PokerHand::FlushFive
-        | PokerHand::FullHouse => cards.to_vec(),
     }
-}
-
-/// Analyses a hand of cards to determine what poker hand conditions exist
-/// This is useful for jokers that activate based on the presence of certain hand conditions
-#[derive(Debug, Default)]
-pub struct HandConditions {
-    pub contains_pair: bool,
-    pub contains_two_pair: bool,
-    pub contains_three_of_a_kind: bool,
-    pub contains_straight: bool,
-    pub contains_flush: bool,
-}
-
-/// Analyses a hand of cards to determine what poker hand conditions exist
-/// This is useful for jokers that activate based on the presence of certain hand conditions
-pub fn analyse_hand_conditions(
-    cards: &[Card],
-    four_fingers_active: bool,
-    shortcut_active: bool,
-    smeared_joker_active: bool,
-) -> GameResult<HandConditions> {
-    let mut conditions = HandConditions::default();
-
-    let rank_counts = group_rank(cards);
-    let mut different_pairs = std::collections::HashSet::new();
 
-    for (&rank, &count) in &rank_counts {
-        if count >= 2 {
-            conditions.contains_pair = true;
-            different_pairs.insert(rank);
-        }
+    // Suits of the same color are two apart (Spades, Hearts, Clubs, Diamonds)
+    let suited_cards = (0..suit_counts.len())
+        .map(|suit| {
+            let smeared = if smeared_joker_active {
+                suit_counts[suit ^ 2]
+            } else {
+                0
+            };
+            suit_counts[suit] + smeared + wild_cards
+        })
+        .max()
+        .unwrap_or(0) as usize;
+    let flush_size = if four_fingers_active {
+        FOUR_FINGERS_HAND_SIZE
+    } else {
+        FULL_HAND_SIZE
+    };
+    let flush = suited_cards >= flush_size;
 
-        if count >= 3 {
-            conditions.contains_three_of_a_kind = true;
-        }
+    let straights = STRAIGHT_TABLE[rank_mask];
+    let mut allowed = STRAIGHT;
+    if shortcut_active {
+        allowed |= SHORTCUT_STRAIGHT;
     }
-    if cards.len() == 2 && rank_counts.len() == 1 {
-        conditions.contains_pair = true;
+    if four_fingers_active {
+        allowed |= FOUR_CARD_STRAIGHT;
+        if shortcut_active {
+            allowed |= FOUR_CARD_SHORTCUT_STRAIGHT;
+        }
     }
+    let straight = straights & allowed != 0;
 
-    conditions.contains_two_pair = different_pairs.len() >= 2;
-
-    conditions.contains_straight = is_straight(cards)
-        || (four_fingers_active && has_four_card_straight(cards))
-        || (shortcut_active && has_shortcut_straight(cards))
-        || (four_fingers_active && shortcut_active && has_four_card_shortcut_straight(cards));
-
-    conditions.contains_flush = is_flush(cards, smeared_joker_active)
-        || (four_fingers_active && has_four_card_flush(cards, smeared_joker_active));
+    let groups_of = |size: u8| rank_counts.iter().filter(|&&count| count == size).count();
+    let distinct_ranks = rank_mask.count_ones();
+    let all_same_rank = distinct_ranks == 1 && cards.len() >= FULL_HAND_SIZE;
+    let three_two = distinct_ranks == 2 && groups_of(3) == 1 && groups_of(2) == 1;
 
-    Ok(conditions)
+    let hand = if all_same_rank && flush {
+        PokerHand::FlushFive
+    } else if three_two && flush {
+        PokerHand::FlushHouse
+    } else if all_same_rank {
+        PokerHand::FiveOfAKind
+    } else if straight && flush {
+        PokerHand::StraightFlush
+    } else if groups_of(4) > 0 {
+        PokerHand::FourOfAKind
+    } else if three_two {
+        PokerHand::FullHouse
+    } else if flush {
+        PokerHand::Flush
+    } else if straight {
+        PokerHand::Straight
+    } else if groups_of(3) > 0 {
+        PokerHand::ThreeOfAKind
+    } else if groups_of(2) >= 2 {
+        PokerHand::TwoPair
+    } else if groups_of(2) == 1 {
+        PokerHand::Pair
+    } else {
+        PokerHand::HighCard
+    };
+    Ok(hand)
 }
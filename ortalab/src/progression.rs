@@ -0,0 +1,481 @@
+//! # Progression Module
+//!
+//! This module simulates whole runs from ante 1 to ante 8 to find out how often
+//! a deck and a set of jokers survive them.
+//!
+//! ## Key Components
+//! - `ProgressionConfig`: The YAML description of the runs (stake, deck, jokers, hands, discards, policy)
+//! - `Policy`: Trait deciding which cards to play or discard against a blind
+//! - `GreedyPolicy` / `RandomPolicy`: The built-in policies
+//! - `simulate_progression()`: Plays one run until it clears every ante or fails a blind
+//! - `survival()`: Plays a run for each of many seeds and reports the survival rate
+//!
+//! ## Run Structure
+//! Each ante has a Small, Big and Boss blind, with targets that depend on the ante,
+//! the stake and the deck variant. For every blind the deck is shuffled and a hand
+//! is dealt. The policy then plays or discards up to five cards at a time, and the
+//! hand is refilled from the deck after each action. Every played hand is scored
+//! with `GameState::score`, and the blind is cleared once the scores add up to its
+//! target. Running out of hands (or of cards to play) before that ends the run.
+//!
+//! Unlike the economy simulation there is no money or shop: the jokers stay the
+//! same for the whole run.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use itertools::Itertools;
+use ortalib::{Card, JokerCard, Round};
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::deck::{Deck, DeckSpec};
+use crate::economy::{Blind, MAX_PLAYED_CARDS, default_antes, default_hand_size, default_hands};
+use crate::errors::{GameError, GameResult};
+use crate::game::GameState;
+use crate::jokers::registry::{CustomJoker, JokerSpec};
+use crate::variants::{Stake, blind_target};
+
+fn default_discards() -> u32 {
+    3
+}
+
+fn default_runs() -> usize {
+    100
+}
+
+/// The built-in play policies that can be selected from a progression file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyConfig {
+    /// Play the highest scoring cards every time
+    #[default]
+    Greedy,
+    /// Play or discard random cards
+    Random,
+}
+
+impl PolicyConfig {
+    /// Creates the play policy described by this configuration for a run's seed
+    pub fn create(&self, seed: u64) -> Box<dyn Policy> {
+        match self {
+            PolicyConfig::Greedy => Box::new(GreedyPolicy),
+            PolicyConfig::Random => Box::new(RandomPolicy::new(seed)),
+        }
+    }
+}
+
+impl fmt::Display for PolicyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyConfig::Greedy => f.pad("greedy"),
+            PolicyConfig::Random => f.pad("random"),
+        }
+    }
+}
+
+/// Describes the runs to simulate
+///
+/// ```yaml
+/// seed: 7
+/// runs: 200
+/// stake: red
+/// discards: 3
+/// jokers:
+///   - Joker
+///   - Blueprint
+///   - Blue Joker
+/// deck:
+///   variant: plasma
+/// policy: random
+/// ```
+///
+/// The runs use the seeds `seed`, `seed + 1`, and so on.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProgressionConfig {
+    /// Seed of the first run
+    #[serde(default)]
+    pub seed: u64,
+    /// Number of runs to simulate
+    #[serde(default = "default_runs")]
+    pub runs: usize,
+    /// Stake the runs are played at
+    #[serde(default)]
+    pub stake: Stake,
+    /// Number of antes to clear
+    #[serde(default = "default_antes")]
+    pub antes: usize,
+    /// Number of cards held in hand
+    #[serde(default = "default_hand_size")]
+    pub hand_size: usize,
+    /// Hands available per blind
+    #[serde(default = "default_hands")]
+    pub hands: u32,
+    /// Discards available per blind
+    #[serde(default = "default_discards")]
+    pub discards: u32,
+    /// Jokers owned for the whole run, built-in or registered
+    #[serde(default)]
+    pub jokers: Vec<JokerSpec>,
+    #[serde(default)]
+    pub deck: DeckSpec,
+    #[serde(default)]
+    pub policy: PolicyConfig,
+}
+
+/// A decision made by a play policy
+///
+/// Indices refer to the cards in hand as shown in the `BlindView`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayAction {
+    /// Play the cards at these indices as a poker hand
+    Play(Vec<usize>),
+    /// Discard the cards at these indices and draw replacements
+    Discard(Vec<usize>),
+}
+
+/// Everything a play policy can see when facing a blind
+pub struct BlindView<'a> {
+    pub ante: usize,
+    pub blind: Blind,
+    /// Score needed to clear the blind
+    pub target: f64,
+    /// Score of the hands already played against the blind
+    pub score: f64,
+    pub hands_left: u32,
+    pub discards_left: u32,
+    pub hand: &'a [Card],
+    pub jokers: &'a [JokerCard],
+    /// The registered jokers among `jokers`, under their cards
+    custom_jokers: &'a HashMap<JokerCard, CustomJoker>,
+    deck: &'a Deck,
+    full_deck: &'a [Card],
+    config: &'a ProgressionConfig,
+}
+
+impl BlindView<'_> {
+    /// The cards left to draw, from bottom to top
+    pub fn remaining_deck(&self) -> &[Card] {
+        self.deck.cards()
+    }
+
+    /// Score of playing the cards at `played`, keeping the rest of the hand
+    pub fn score_play(&self, played: &[usize]) -> GameResult<f64> {
+        check_selection(played, self.hand.len())?;
+        let round = Round {
+            cards_played: played.iter().map(|&i| self.hand[i]).collect(),
+            cards_held_in_hand: (0..self.hand.len())
+                .filter(|i| !played.contains(i))
+                .map(|i| self.hand[i])
+                .collect(),
+            jokers: self.jokers.to_vec(),
+        };
+        let mut game = GameState::new(round, false);
+        game.custom_jokers = self.custom_jokers.clone();
+        game.card_extras = self.deck.card_extras().clone();
+        game.deck_variant = self.config.deck.variant;
+        game.deck = Some(self.full_deck.to_vec());
+        game.remaining_deck = Some(self.deck.cards().to_vec());
+
+        let (chips, mult) = game.score()?;
+        Ok((chips * mult).floor())
+    }
+}
+
+/// Decides which cards to play or discard against a blind
+pub trait Policy {
+    fn decide(&mut self, view: &BlindView) -> GameResult<PlayAction>;
+}
+
+/// Plays the highest scoring selection of up to five cards, never discarding
+pub struct GreedyPolicy;
+
+impl Policy for GreedyPolicy {
+    fn decide(&mut self, view: &BlindView) -> GameResult<PlayAction> {
+        let mut best: Option<(f64, Vec<usize>)> = None;
+        for size in 1..=MAX_PLAYED_CARDS.min(view.hand.len()) {
+            for played in (0..view.hand.len()).combinations(size) {
+                let score = view.score_play(&played)?;
+                if best.as_ref().is_none_or(|(best, _)| score > *best) {
+                    best = Some((score, played));
+                }
+            }
+        }
+
+        best.map(|(_, played)| PlayAction::Play(played))
+            .ok_or_else(|| GameError::InvalidHand("no cards are left in hand".to_string()))
+    }
+}
+
+/// Discards or plays between one and five random cards, with even odds while
+/// discards are left
+pub struct RandomPolicy {
+    rng: StdRng,
+}
+
+impl RandomPolicy {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Policy for RandomPolicy {
+    fn decide(&mut self, view: &BlindView) -> GameResult<PlayAction> {
+        if view.hand.is_empty() {
+            return Err(GameError::InvalidHand(
+                "no cards are left in hand".to_string(),
+            ));
+        }
+        let size = self
+            .rng
+            .random_range(1..=MAX_PLAYED_CARDS.min(view.hand.len()));
+        let cards = sample(&mut self.rng, view.hand.len(), size).into_vec();
+
+        if view.discards_left > 0 && self.rng.random_bool(0.5) {
+            Ok(PlayAction::Discard(cards))
+        } else {
+            Ok(PlayAction::Play(cards))
+        }
+    }
+}
+
+/// Checks that `selected` picks between one and five distinct cards of a hand
+fn check_selection(selected: &[usize], hand_size: usize) -> GameResult<()> {
+    if selected.is_empty() || selected.len() > MAX_PLAYED_CARDS {
+        return Err(GameError::IllegalAction(format!(
+            "between 1 and {} cards must be selected, not {}",
+            MAX_PLAYED_CARDS,
+            selected.len()
+        )));
+    }
+    if let Some(&index) = selected.iter().find(|&&index| index >= hand_size) {
+        return Err(GameError::IllegalAction(format!(
+            "card {} is not in a hand of {} cards",
+            index, hand_size
+        )));
+    }
+    if !selected.iter().all_unique() {
+        return Err(GameError::IllegalAction(
+            "the same card cannot be selected twice".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// How a single run ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunOutcome {
+    pub seed: u64,
+    pub antes_cleared: usize,
+    /// The blind that ended the run, or `None` if every ante was cleared
+    pub lost_at: Option<(usize, Blind)>,
+}
+
+/// A run in progress
+struct Run<'a> {
+    config: &'a ProgressionConfig,
+    jokers: Vec<JokerCard>,
+    custom_jokers: HashMap<JokerCard, CustomJoker>,
+    deck: Deck,
+    rng: StdRng,
+}
+
+impl Run<'_> {
+    /// Plays and discards against a blind until it is cleared or the hands run out,
+    /// returning whether it was cleared
+    fn play_blind(
+        &mut self,
+        ante: usize,
+        blind: Blind,
+        policy: &mut dyn Policy,
+    ) -> GameResult<bool> {
+        let config = self.config;
+        let target = blind_target(ante, blind, config.stake, config.deck.variant);
+
+        let mut deck = self.deck.clone();
+        deck.shuffle(self.rng.random());
+        let full_deck = deck.cards().to_vec();
+
+        let mut hand = Vec::new();
+        deck.draw_to_hand_size(&mut hand, config.hand_size);
+        let mut score = 0.0;
+        let mut hands_left = config.hands;
+        let mut discards_left = config.discards;
+
+        while score < target && hands_left > 0 && !hand.is_empty() {
+            let view = BlindView {
+                ante,
+                blind,
+                target,
+                score,
+                hands_left,
+                discards_left,
+                hand: &hand,
+                jokers: &self.jokers,
+                custom_jokers: &self.custom_jokers,
+                deck: &deck,
+                full_deck: &full_deck,
+                config,
+            };
+            let selected = match policy.decide(&view)? {
+                PlayAction::Play(played) => {
+                    score += view.score_play(&played)?;
+                    hands_left -= 1;
+                    played
+                }
+                PlayAction::Discard(discarded) => {
+                    if discards_left == 0 {
+                        return Err(GameError::IllegalAction(format!(
+                            "cannot discard against the {} of ante {} with no discards left",
+                            blind, ante
+                        )));
+                    }
+                    check_selection(&discarded, hand.len())?;
+                    discards_left -= 1;
+                    discarded
+                }
+            };
+
+            for index in selected.into_iter().sorted().rev() {
+                hand.remove(index);
+            }
+            deck.draw_to_hand_size(&mut hand, config.hand_size);
+        }
+
+        Ok(score >= target)
+    }
+}
+
+/// Simulates one run with `seed`, stopping at the first blind that is not cleared
+pub fn simulate_progression(
+    config: &ProgressionConfig,
+    seed: u64,
+    policy: &mut dyn Policy,
+) -> GameResult<RunOutcome> {
+    let mut custom_jokers = HashMap::new();
+    let jokers = config
+        .jokers
+        .iter()
+        .map(|spec| spec.clone().into_joker_card(&mut custom_jokers))
+        .collect();
+    let mut run = Run {
+        config,
+        jokers,
+        custom_jokers,
+        deck: Deck::from_spec(&config.deck)?,
+        rng: StdRng::seed_from_u64(seed),
+    };
+
+    for ante in 1..=config.antes {
+        for blind in Blind::ALL {
+            if !run.play_blind(ante, blind, policy)? {
+                return Ok(RunOutcome {
+                    seed,
+                    antes_cleared: ante - 1,
+                    lost_at: Some((ante, blind)),
+                });
+            }
+        }
+    }
+
+    Ok(RunOutcome {
+        seed,
+        antes_cleared: config.antes,
+        lost_at: None,
+    })
+}
+
+/// How many runs ended at one blind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlindLosses {
+    pub ante: usize,
+    pub blind: Blind,
+    pub runs: usize,
+}
+
+/// The outcome of many runs
+#[derive(Debug, Clone)]
+pub struct SurvivalReport {
+    pub policy: PolicyConfig,
+    pub runs: usize,
+    /// Runs that cleared every ante
+    pub survived: usize,
+    /// Blinds that ended at least one run, in the order they are played
+    pub losses: Vec<BlindLosses>,
+}
+
+impl SurvivalReport {
+    /// Fraction of the runs that cleared every ante
+    pub fn survival_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.survived as f64 / self.runs as f64
+        }
+    }
+}
+
+impl fmt::Display for SurvivalReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Survived {} of {} runs ({:.1}%) with the {} policy",
+            self.survived,
+            self.runs,
+            self.survival_rate() * 100.0,
+            self.policy
+        )?;
+        for losses in &self.losses {
+            writeln!(
+                f,
+                "Lost at ante {} {}: {} run{}",
+                losses.ante,
+                losses.blind,
+                losses.runs,
+                if losses.runs == 1 { "" } else { "s" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Simulates `config.runs` runs with the configured policy and counts how many survive
+pub fn survival(config: &ProgressionConfig) -> GameResult<SurvivalReport> {
+    let mut survived = 0;
+    let mut losses: Vec<BlindLosses> = Vec::new();
+
+    for run in 0..config.runs {
+        let seed = config.seed.wrapping_add(run as u64);
+        let mut policy = config.policy.create(seed);
+        match simulate_progression(config, seed, policy.as_mut())?.lost_at {
+            None => survived += 1,
+            Some((ante, blind)) => {
+                match losses
+                    .iter_mut()
+                    .find(|losses| losses.ante == ante && losses.blind == blind)
+                {
+                    Some(losses) => losses.runs += 1,
+                    None => losses.push(BlindLosses {
+                        ante,
+                        blind,
+                        runs: 1,
+                    }),
+                }
+            }
+        }
+    }
+
+    let blind_order = |blind: Blind| Blind::ALL.iter().position(|&b| b == blind);
+    losses.sort_by_key(|losses| (losses.ante, blind_order(losses.blind)));
+
+    Ok(SurvivalReport {
+        policy: config.policy,
+        runs: config.runs,
+        survived,
+        losses,
+    })
+}
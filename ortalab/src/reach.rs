@@ -0,0 +1,334 @@
+//! # Reach Module
+//!
+//! This module works backwards from a target score: it searches for the fewest
+//! changes to a round that make it score at least the target.
+//!
+//! ## Key Components
+//! - `Change`: One change to the round (a joker edition, a card enhancement or an extra joker)
+//! - `SearchSpace`: Which changes may be tried
+//! - `reach()`: Tries every combination of 1, 2, ... changes until one reaches the target
+//!
+//! ## Search
+//! Every combination of candidate changes is scored with `GameState::score`,
+//! smallest combinations first, so the first size with any solution is the
+//! minimal number of changes. Each card and joker is changed at most once,
+//! and added jokers go to the right of the existing ones, up to the joker slots.
+//! Negative jokers keep their edition, since changing it would take a joker slot.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use enum_iterator::all;
+use itertools::Itertools;
+use ortalib::{Card, Edition, Enhancement, Joker, JokerCard, Round};
+
+use crate::consumables::{ConsumableUse, Zone};
+use crate::errors::GameResult;
+use crate::extras::CardExtras;
+use crate::game::GameState;
+use crate::history::RunHistory;
+use crate::jokers::registry::CustomJoker;
+use crate::levels::HandLevels;
+use crate::round::RoundInput;
+use crate::variants::DeckVariant;
+
+/// A single change to a round
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Gives the joker at `index` a different edition
+    JokerEdition {
+        index: usize,
+        name: String,
+        edition: Edition,
+    },
+    /// Gives the played or held card at `index` a different enhancement
+    CardEnhancement {
+        zone: Zone,
+        index: usize,
+        card: Card,
+        enhancement: Enhancement,
+    },
+    /// Adds a joker from the pool to the right of the others
+    AddJoker(Joker),
+}
+
+impl Change {
+    /// Changes conflict when they change the same card or joker
+    fn conflicts_with(&self, other: &Change) -> bool {
+        match (self, other) {
+            (Change::JokerEdition { index: a, .. }, Change::JokerEdition { index: b, .. }) => {
+                a == b
+            }
+            (
+                Change::CardEnhancement {
+                    zone: zone_a,
+                    index: a,
+                    ..
+                },
+                Change::CardEnhancement {
+                    zone: zone_b,
+                    index: b,
+                    ..
+                },
+            ) => zone_a == zone_b && a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::JokerEdition {
+                index,
+                name,
+                edition,
+            } => write!(f, "Make joker {} ({}) {}", index + 1, name, edition),
+            Change::CardEnhancement {
+                zone,
+                index,
+                card,
+                enhancement,
+            } => {
+                let zone = match zone {
+                    Zone::Played => "played",
+                    Zone::Held => "held",
+                };
+                write!(
+                    f,
+                    "Make {} card {} ({}{}) {}",
+                    zone,
+                    index + 1,
+                    card.rank,
+                    card.suit,
+                    enhancement
+                )
+            }
+            Change::AddJoker(joker) => write!(f, "Add {}", joker),
+        }
+    }
+}
+
+/// The changes the search may try
+#[derive(Debug, Clone)]
+pub struct SearchSpace {
+    /// Try other editions on the existing jokers
+    pub editions: bool,
+    /// Try other enhancements on the played and held cards
+    pub enhancements: bool,
+    /// Jokers that may be added, each at most once per entry
+    pub pool: Vec<Joker>,
+    /// The most jokers the round may end up with, not counting Negative jokers
+    pub joker_slots: usize,
+    /// The most changes in one solution
+    pub max_changes: usize,
+}
+
+/// A set of changes and the score it reaches
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub changes: Vec<Change>,
+    pub score: f64,
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Score {}:", self.score)?;
+        for change in &self.changes {
+            writeln!(f, "  {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a search
+#[derive(Debug, Clone)]
+pub struct ReachResult {
+    pub target: f64,
+    /// Score of the round without any change
+    pub current: f64,
+    /// Every solution with the fewest changes, best score first
+    pub solutions: Vec<Solution>,
+    /// Number of change combinations that were scored
+    pub tried: usize,
+}
+
+/// The round being searched, as read from the round file
+struct BaseRound<'a> {
+    round: &'a Round,
+    card_extras: &'a HashMap<Card, CardExtras>,
+    custom_jokers: &'a HashMap<JokerCard, CustomJoker>,
+    consumables: &'a [ConsumableUse],
+    seed: Option<u64>,
+    deck_variant: DeckVariant,
+    history: &'a RunHistory,
+    hand_levels: &'a HandLevels,
+    deck: &'a Option<Vec<Card>>,
+    remaining_deck: &'a Option<Vec<Card>>,
+}
+
+impl BaseRound<'_> {
+    /// Scores the round with `changes` applied
+    fn score(&self, changes: &[&Change]) -> GameResult<f64> {
+        let mut round = Round {
+            cards_played: self.round.cards_played.clone(),
+            cards_held_in_hand: self.round.cards_held_in_hand.clone(),
+            jokers: self.round.jokers.clone(),
+        };
+        let mut card_extras = self.card_extras.clone();
+        let mut custom_jokers = self.custom_jokers.clone();
+
+        for change in changes {
+            match **change {
+                Change::JokerEdition { index, edition, .. } => {
+                    // Like cards, a custom joker's edition is part of its identity
+                    let custom = custom_jokers.remove(&round.jokers[index]);
+                    round.jokers[index].edition = Some(edition);
+                    if let Some(custom) = custom {
+                        custom_jokers.insert(round.jokers[index], custom);
+                    }
+                }
+                Change::CardEnhancement {
+                    zone,
+                    index,
+                    enhancement,
+                    ..
+                } => {
+                    let cards = match zone {
+                        Zone::Played => &mut round.cards_played,
+                        Zone::Held => &mut round.cards_held_in_hand,
+                    };
+                    // A card's enhancement is part of its identity, so its extras move with it
+                    let extras = card_extras.remove(&cards[index]);
+                    cards[index].enhancement = Some(enhancement);
+                    if let Some(extras) = extras {
+                        card_extras.insert(cards[index], extras);
+                    }
+                }
+                Change::AddJoker(joker) => round.jokers.push(JokerCard::new(joker, None)),
+            }
+        }
+
+        let mut game = GameState::new(round, false);
+        game.card_extras = card_extras;
+        game.custom_jokers = custom_jokers;
+        game.deck_variant = self.deck_variant;
+        game.history = self.history.clone();
+        game.hand_levels = self.hand_levels.clone();
+        game.deck = self.deck.clone();
+        game.remaining_deck = self.remaining_deck.clone();
+        if let Some(seed) = self.seed {
+            game.seed(seed);
+        }
+        game.use_consumables(self.consumables)?;
+        let (chips, mult) = game.score()?;
+        Ok((chips * mult).floor())
+    }
+}
+
+/// Lists every single change the search may try
+fn candidate_changes(input: &RoundInput, space: &SearchSpace) -> Vec<Change> {
+    let round = &input.round;
+    let mut changes = Vec::new();
+
+    if space.editions {
+        for (index, joker_card) in round.jokers.iter().enumerate() {
+            if input.negative_jokers.contains(joker_card) {
+                continue;
+            }
+            let name = match input.custom_jokers.get(joker_card) {
+                Some(custom) => custom.name.to_string(),
+                None => joker_card.joker.to_string(),
+            };
+            for edition in all::<Edition>().filter(|&e| Some(e) != joker_card.edition) {
+                changes.push(Change::JokerEdition {
+                    index,
+                    name: name.clone(),
+                    edition,
+                });
+            }
+        }
+    }
+
+    if space.enhancements {
+        let zones = [
+            (Zone::Played, &round.cards_played),
+            (Zone::Held, &round.cards_held_in_hand),
+        ];
+        for (zone, cards) in zones {
+            for (index, card) in cards.iter().enumerate() {
+                for enhancement in all::<Enhancement>().filter(|&e| Some(e) != card.enhancement) {
+                    changes.push(Change::CardEnhancement {
+                        zone,
+                        index,
+                        card: *card,
+                        enhancement,
+                    });
+                }
+            }
+        }
+    }
+
+    changes.extend(space.pool.iter().map(|&joker| Change::AddJoker(joker)));
+    changes
+}
+
+/// Finds the fewest changes that make the round score at least `target`
+pub fn reach(input: &RoundInput, target: f64, space: &SearchSpace) -> GameResult<ReachResult> {
+    let base = BaseRound {
+        round: &input.round,
+        card_extras: &input.card_extras,
+        custom_jokers: &input.custom_jokers,
+        consumables: &input.consumables,
+        seed: input.seed,
+        deck_variant: input.deck_variant,
+        history: &input.history,
+        hand_levels: &input.hand_levels,
+        deck: &input.deck,
+        remaining_deck: &input.remaining_deck,
+    };
+    let current = base.score(&[])?;
+    let mut result = ReachResult {
+        target,
+        current,
+        solutions: Vec::new(),
+        tried: 0,
+    };
+    if current >= target {
+        return Ok(result);
+    }
+
+    let candidates = candidate_changes(input, space);
+    let free_slots = space.joker_slots.saturating_sub(input.jokers_in_slots());
+
+    for size in 1..=space.max_changes.min(candidates.len()) {
+        for changes in candidates.iter().combinations(size) {
+            let added = changes
+                .iter()
+                .filter(|change| matches!(change, Change::AddJoker(_)))
+                .count();
+            let conflicting = changes
+                .iter()
+                .tuple_combinations()
+                .any(|(a, b)| a.conflicts_with(b));
+            if added > free_slots || conflicting {
+                continue;
+            }
+
+            result.tried += 1;
+            let score = base.score(&changes)?;
+            if score >= target {
+                result.solutions.push(Solution {
+                    changes: changes.into_iter().cloned().collect(),
+                    score,
+                });
+            }
+        }
+
+        if !result.solutions.is_empty() {
+            result.solutions.sort_by(|a, b| b.score.total_cmp(&a.score));
+            break;
+        }
+    }
+    Ok(result)
+}
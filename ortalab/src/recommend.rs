@@ -0,0 +1,282 @@
+//! # Recommend Module
+//!
+//! This module ranks the jokers offered in the shop by how much they would add
+//! to the score of the hands to come.
+//!
+//! The hands to come are sampled by shuffling the round's deck (its full `deck`,
+//! or the 52 cards of its deck variant) and dealing a hand from it. Every hand is
+//! played the best way it can be, as in the economy simulation, first with the
+//! current jokers and then with each candidate. All candidates are tried on the
+//! same hands, so their expected scores can be compared directly.
+//!
+//! Jokers can be rearranged at any time, so a candidate is tried at every
+//! position among the current jokers (Blueprint only helps left of another
+//! joker, and x Mult is worth more after + Mult). When every joker slot is taken,
+//! it also replaces whichever current joker is best to give up; Negative
+//! candidates need no slot.
+//!
+//! Each candidate is listed with its shop price, which for a registered joker
+//! is its own cost rather than that of the card standing for it.
+//!
+//! ## Key Components
+//! - `recommend()`: Ranks the candidates by expected score gain
+//! - `Recommendations`: The ranking printed by `ortalab recommend`
+
+use std::collections::HashMap;
+use std::fmt;
+
+use itertools::Itertools;
+use ortalib::{Card, JokerCard};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::deck::Deck;
+use crate::economy::{best_play, joker_cost};
+use crate::errors::{GameError, GameResult};
+use crate::jokers::registry::{CustomJoker, JokerKind, JokerSpec};
+use crate::round::RoundInput;
+
+/// How the hands to come are sampled
+#[derive(Debug, Clone, Copy)]
+pub struct RecommendOptions {
+    /// Number of hands dealt
+    pub samples: usize,
+    /// Number of cards in each hand
+    pub hand_size: usize,
+    /// Seed used to shuffle the deck for every hand
+    pub seed: u64,
+}
+
+/// One candidate's place in the ranking
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub joker: String,
+    /// Shop price of the candidate, including its edition
+    pub cost: u32,
+    /// Average best score of the sampled hands with the candidate
+    pub expected: f64,
+    /// `expected` minus the average best score with the current jokers
+    pub gain: f64,
+    /// Where the candidate goes among the jokers, from 1 (leftmost)
+    pub position: usize,
+    /// The current joker given up to make room, if the joker slots are full
+    pub replaces: Option<String>,
+}
+
+/// The candidates ranked from the largest expected score gain to the smallest
+#[derive(Debug, Clone)]
+pub struct Recommendations {
+    pub jokers: Vec<String>,
+    /// Average best score of the sampled hands with the current jokers
+    pub baseline: f64,
+    pub samples: usize,
+    pub ranked: Vec<Recommendation>,
+}
+
+impl fmt::Display for Recommendations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let jokers = if self.jokers.is_empty() {
+            "none".to_string()
+        } else {
+            self.jokers.join(", ")
+        };
+        writeln!(
+            f,
+            "Current jokers: {} (expected score {:.1} over {} hands)",
+            jokers, self.baseline, self.samples
+        )?;
+        for (rank, recommendation) in self.ranked.iter().enumerate() {
+            write!(
+                f,
+                "{:>2}. {:<24} {:>4}  expected {:>10.1}  gain {:>+10.1}  at position {}",
+                rank + 1,
+                recommendation.joker,
+                format!("${}", recommendation.cost),
+                recommendation.expected,
+                recommendation.gain,
+                recommendation.position
+            )?;
+            if let Some(replaced) = &recommendation.replaces {
+                write!(f, "  (replaces {})", replaced)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A hand to come, with the deck it was dealt from
+struct SampledHand {
+    hand: Vec<Card>,
+    remaining_deck: Vec<Card>,
+}
+
+/// A joker as written in a round file, e.g. `Baron Holographic` or `Supernova Negative`
+fn spec_label(spec: &JokerSpec) -> String {
+    let label = match &spec.kind {
+        JokerKind::Builtin(joker_card) => joker_card.to_string(),
+        JokerKind::Custom(custom, Some(edition)) => format!("{} {}", custom.name, edition),
+        JokerKind::Custom(custom, None) => custom.name.to_string(),
+    };
+    if spec.negative {
+        format!("{} Negative", label)
+    } else {
+        label
+    }
+}
+
+/// One of the round's jokers as written in its round file
+fn joker_label(input: &RoundInput, joker_card: &JokerCard) -> String {
+    let mut label = match input.custom_jokers.get(joker_card) {
+        Some(custom) => custom.name.to_string(),
+        None => joker_card.joker.to_string(),
+    };
+    if let Some(edition) = joker_card.edition {
+        label = format!("{} {}", label, edition);
+    }
+    if input.negative_jokers.contains(joker_card) {
+        label = format!("{} Negative", label);
+    }
+    label
+}
+
+/// Scores sampled hands with the rest of the round as read from its file
+struct Sampler<'a> {
+    input: &'a RoundInput,
+    full_deck: Vec<Card>,
+    hands: Vec<SampledHand>,
+}
+
+impl Sampler<'_> {
+    /// Average best score of the sampled hands with `jokers`
+    fn expected_score(
+        &self,
+        jokers: &[JokerCard],
+        custom_jokers: &HashMap<JokerCard, CustomJoker>,
+    ) -> GameResult<f64> {
+        let input = self.input;
+        let mut total = 0.0;
+        for sampled in &self.hands {
+            let play = best_play(&sampled.hand, jokers, |game| {
+                game.card_extras = input.card_extras.clone();
+                game.custom_jokers = custom_jokers.clone();
+                game.deck_variant = input.deck_variant;
+                game.history = input.history.clone();
+                game.hand_levels = input.hand_levels.clone();
+                game.deck = Some(self.full_deck.clone());
+                game.remaining_deck = Some(sampled.remaining_deck.clone());
+            })?;
+            total += play.score;
+        }
+        Ok(total / self.hands.len() as f64)
+    }
+}
+
+/// Ranks `candidates` by how much each adds to the expected score of the round's jokers
+pub fn recommend(
+    input: &RoundInput,
+    candidates: Vec<JokerSpec>,
+    options: &RecommendOptions,
+) -> GameResult<Recommendations> {
+    let deck = match &input.deck {
+        Some(cards) => Deck::from_cards(cards.clone()),
+        None => Deck::from_variant(input.deck_variant, options.seed),
+    }
+    .with_extras(&input.card_extras);
+    if deck.is_empty() {
+        return Err(GameError::InvalidDeck(
+            "no hands can be sampled from an empty deck".to_string(),
+        ));
+    }
+    if options.samples == 0 {
+        return Err(GameError::IllegalAction(
+            "at least one hand must be sampled".to_string(),
+        ));
+    }
+
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let hands = (0..options.samples)
+        .map(|_| {
+            let mut deck = deck.clone();
+            deck.shuffle(rng.random());
+            let mut hand = Vec::new();
+            deck.draw_to_hand_size(&mut hand, options.hand_size);
+            SampledHand {
+                hand,
+                remaining_deck: deck.cards().to_vec(),
+            }
+        })
+        .collect();
+    let sampler = Sampler {
+        input,
+        full_deck: deck.cards().to_vec(),
+        hands,
+    };
+
+    let jokers = &input.round.jokers;
+    let baseline = sampler.expected_score(jokers, &input.custom_jokers)?;
+    let slots_full = input.jokers_in_slots() >= input.joker_slots;
+
+    let mut ranked = Vec::new();
+    for spec in candidates {
+        let label = spec_label(&spec);
+        let needs_slot = slots_full && !spec.negative;
+        let mut custom_jokers = input.custom_jokers.clone();
+        let candidate = spec.into_joker_card(&mut custom_jokers);
+        let cost = joker_cost(&candidate, custom_jokers.get(&candidate));
+
+        // Without a free slot, try giving up each joker that takes one
+        let replaceable: Vec<Option<usize>> = if needs_slot {
+            (0..jokers.len())
+                .filter(|&i| !input.negative_jokers.contains(&jokers[i]))
+                .map(Some)
+                .collect()
+        } else {
+            vec![None]
+        };
+        if replaceable.is_empty() {
+            return Err(GameError::IllegalAction(format!(
+                "{} needs a joker slot, but the round has none",
+                label
+            )));
+        }
+
+        let mut best: Option<Recommendation> = None;
+        for replaced in replaceable {
+            let mut kept = jokers.clone();
+            if let Some(index) = replaced {
+                kept.remove(index);
+            }
+            for position in 0..=kept.len() {
+                let mut with_candidate = kept.clone();
+                with_candidate.insert(position, candidate);
+                let expected = sampler.expected_score(&with_candidate, &custom_jokers)?;
+                // Ties keep the first arrangement tried, leftmost and replacing the leftmost
+                if best.as_ref().is_none_or(|best| expected > best.expected) {
+                    best = Some(Recommendation {
+                        joker: label.clone(),
+                        cost,
+                        expected,
+                        gain: expected - baseline,
+                        position: position + 1,
+                        replaces: replaced.map(|index| joker_label(input, &jokers[index])),
+                    });
+                }
+            }
+        }
+        ranked.extend(best);
+    }
+
+    Ok(Recommendations {
+        jokers: jokers
+            .iter()
+            .map(|joker_card| joker_label(input, joker_card))
+            .collect(),
+        baseline,
+        samples: options.samples,
+        ranked: ranked
+            .into_iter()
+            .sorted_by(|a, b| b.gain.total_cmp(&a.gain))
+            .collect(),
+    })
+}
@@ -0,0 +1,123 @@
+//! # Replay Module
+//!
+//! This module defines replay files, which capture a scoring run so it can be
+//! re-run later and checked step by step (e.g. when a player reports a bug).
+//!
+//! A replay holds the round exactly as read, the engine version, the RNG seed,
+//! the balance file if the run used one, every explain line produced while
+//! scoring and the final outcome. Replays are written as JSON but, like any
+//! other input, can be read in any input format.
+//!
+//! ## Key Components
+//! - `Replay`: The contents of a replay file
+//! - `Replay::check()`: Compares a re-run against the recorded one
+//! - `Divergence`: The first step where a re-run differs from the recording
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Version of the engine that records replays
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How a scoring run ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The final score
+    Score(f64),
+    /// The game error that stopped scoring
+    Error(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Score(score) => write!(f, "score {}", score),
+            Outcome::Error(error) => write!(f, "game error `{}`", error),
+        }
+    }
+}
+
+/// A recorded scoring run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    /// Version of the engine that recorded the run
+    pub version: String,
+    /// Seed for chance-based effects used by the run
+    pub seed: Option<u64>,
+    /// The round file's contents
+    pub round: serde_json::Value,
+    /// The balance file's contents, if the run used one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<serde_json::Value>,
+    /// Every explain line, in order
+    pub trace: Vec<String>,
+    pub outcome: Outcome,
+}
+
+/// Where a re-run first differs from its replay
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// The explain lines differ at `step` (counting from 1); `None` means the trace ended
+    Step {
+        step: usize,
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+    /// The traces match, but the runs ended differently
+    Outcome { expected: Outcome, actual: Outcome },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |line: &Option<String>| match line {
+            Some(line) => format!("`{}`", line),
+            None => "end of trace".to_string(),
+        };
+        match self {
+            Divergence::Step {
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "replay diverges at step {}: expected {}, got {}",
+                step,
+                line(expected),
+                line(actual)
+            ),
+            Divergence::Outcome { expected, actual } => write!(
+                f,
+                "replay diverges at the end: expected {}, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Replay {
+    /// Compares a re-run's trace and outcome against this replay
+    pub fn check(&self, trace: &[String], outcome: &Outcome) -> Result<(), Divergence> {
+        let steps = self.trace.len().max(trace.len());
+        for step in 0..steps {
+            let expected = self.trace.get(step);
+            let actual = trace.get(step);
+            if expected != actual {
+                return Err(Divergence::Step {
+                    step: step + 1,
+                    expected: expected.cloned(),
+                    actual: actual.cloned(),
+                });
+            }
+        }
+
+        if &self.outcome != outcome {
+            return Err(Divergence::Outcome {
+                expected: self.outcome.clone(),
+                actual: outcome.clone(),
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! # Round Module
+//!
+//! This module defines ortalab's round file format.
+//!
+//! The format is ortalib's `Round` (`cards_played`, `cards_held_in_hand`, `jokers`)
+//! with cards written as `CardSpec`s, so they can carry extras such as seals or
+//! the debuffed and face-down states (e.g. `K♠ Debuffed`, `7♣ Face Down`).
+//! It is extended with optional sections that only ortalab understands:
+//! - `consumables`: Tarot and Spectral cards used before the hand is scored
+//! - `seed`: Enables chance-based effects such as Glass cards shattering
+//! - `deck_variant`: The deck variant, e.g. `plasma` to balance Chips and Mult
+//! - `deck`, `remaining_deck`: The full deck and the cards left to draw, for
+//!   jokers such as Blue Joker (either one is enough, the other is worked out from
+//!   it and the played and held cards)
+//! - `history`: Earlier hands of the run, for jokers such as Supernova (see `history`)
+//! - `joker_slots`: How many jokers the round may have (5 by default)
+//! - `hand_levels`: The levels of the poker hands above level 1 (see `levels`)
+//!
+//! `jokers` may also name custom jokers added through `jokers::registry`, and
+//! jokers and cards may have the Negative edition, which ortalib does not model.
+//! Negative jokers take no joker slot, and reading a round with more jokers than
+//! joker slots fails.
+//!
+//! The JSON Schema of the format is generated from `RoundFile` by
+//! `round_schema()` and printed by `ortalab schema`.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use enum_iterator::all;
+use itertools::Itertools;
+use ortalib::{Card, Edition, Joker, JokerCard, Round};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema, schema_for};
+use serde::Deserialize;
+
+use crate::consumables::ConsumableUse;
+use crate::deck::Deck;
+use crate::extras::{CardExtras, CardSpec};
+use crate::history::RunHistory;
+use crate::jokers::registry::{self, CustomJoker, JokerSpec};
+use crate::levels::HandLevels;
+use crate::variants::DeckVariant;
+
+/// Number of joker slots a round has unless its file says otherwise
+pub const DEFAULT_JOKER_SLOTS: usize = 5;
+
+fn default_joker_slots() -> usize {
+    DEFAULT_JOKER_SLOTS
+}
+
+/// A round file exactly as written
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(title = "Round", description = "A hand of Balatro to score")]
+struct RoundFile {
+    /// The cards played this hand, in order
+    cards_played: Vec<CardSpec>,
+    /// The cards left in hand, in order
+    #[serde(default)]
+    cards_held_in_hand: Vec<CardSpec>,
+    /// The jokers, from left to right
+    #[serde(default)]
+    #[schemars(with = "Vec<JokerCardSchema>", extend("default" = []))]
+    jokers: Vec<JokerSpec>,
+    /// Tarot and Spectral cards used before the hand is scored, in order
+    #[serde(default)]
+    consumables: Vec<ConsumableUse>,
+    /// Seed for chance-based effects (no chance-based effects happen without one)
+    #[serde(default)]
+    seed: Option<u64>,
+    /// The deck the round is played with
+    #[serde(default)]
+    deck_variant: DeckVariant,
+    /// Every card of the full deck, including the played and held cards
+    #[serde(default)]
+    deck: Option<Vec<CardSpec>>,
+    /// The cards left to draw
+    #[serde(default)]
+    remaining_deck: Option<Vec<CardSpec>>,
+    /// Earlier hands of the run (the round is the run's first hand without it)
+    #[serde(default)]
+    history: RunHistory,
+    /// How many jokers the round may have, not counting Negative jokers
+    #[serde(default = "default_joker_slots")]
+    joker_slots: usize,
+    /// Levels of the poker hands, from Planet cards (hands left out are level 1)
+    #[serde(default)]
+    hand_levels: HandLevels,
+}
+
+/// The schema of joker strings, e.g. `Blueprint` or `Joker Foil`, including
+/// the names of custom jokers registered so far
+struct JokerCardSchema;
+
+impl JsonSchema for JokerCardSchema {
+    fn schema_name() -> Cow<'static, str> {
+        "JokerCard".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        let custom = registry::custom_jokers();
+        let jokers = all::<Joker>()
+            .map(|joker| joker.to_string())
+            .chain(custom.iter().map(|custom| custom.name.to_string()))
+            .join("|");
+        let editions = all::<Edition>().join("|");
+        json_schema!({
+            "type": "string",
+            "description": "A joker name, optionally followed by its edition",
+            "pattern": format!("^({})( ({}|Negative))?$", jokers, editions),
+            "examples": ["Blueprint", "Joker Foil", "Baron Negative"]
+        })
+    }
+}
+
+/// The JSON Schema of round files
+pub fn round_schema() -> Schema {
+    schema_for!(RoundFile)
+}
+
+/// A round as read from a round file
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "RoundFile")]
+pub struct RoundInput {
+    pub round: Round,
+
+    /// Extras of the played and held cards that have any
+    pub card_extras: HashMap<Card, CardExtras>,
+
+    /// Registered jokers, keyed by the joker cards standing in for them in `round`
+    pub custom_jokers: HashMap<JokerCard, CustomJoker>,
+
+    /// Tarot and Spectral cards used before the hand is scored
+    pub consumables: Vec<ConsumableUse>,
+
+    /// Seed for chance-based effects (no chance-based effects happen without one)
+    pub seed: Option<u64>,
+
+    /// The deck the round is played with
+    pub deck_variant: DeckVariant,
+
+    /// Every card of the full deck, if known
+    pub deck: Option<Vec<Card>>,
+
+    /// The cards left to draw, if known
+    pub remaining_deck: Option<Vec<Card>>,
+
+    /// Earlier hands of the run
+    pub history: RunHistory,
+
+    /// How many jokers the round may have, not counting Negative jokers
+    pub joker_slots: usize,
+
+    /// Jokers with the Negative edition
+    pub negative_jokers: HashSet<JokerCard>,
+
+    /// Levels of the poker hands
+    pub hand_levels: HandLevels,
+}
+
+impl RoundInput {
+    /// Number of jokers that take a joker slot
+    pub fn jokers_in_slots(&self) -> usize {
+        self.round
+            .jokers
+            .iter()
+            .filter(|joker_card| !self.negative_jokers.contains(joker_card))
+            .count()
+    }
+}
+
+impl TryFrom<RoundFile> for RoundInput {
+    type Error = String;
+
+    fn try_from(file: RoundFile) -> Result<Self, Self::Error> {
+        file.hand_levels.check()?;
+        let card_extras = file
+            .cards_played
+            .iter()
+            .chain(&file.cards_held_in_hand)
+            .chain(file.deck.iter().flatten())
+            .chain(file.remaining_deck.iter().flatten())
+            .filter(|spec| spec.extras != CardExtras::default())
+            .map(|spec| (spec.card, spec.extras))
+            .collect();
+        let cards = |specs: Vec<CardSpec>| specs.into_iter().map(|spec| spec.card).collect();
+
+        let mut custom_jokers = HashMap::new();
+        let mut negative_jokers = HashSet::new();
+        let jokers = file
+            .jokers
+            .into_iter()
+            .map(|spec: JokerSpec| {
+                let negative = spec.negative;
+                let joker_card = spec.into_joker_card(&mut custom_jokers);
+                if negative {
+                    negative_jokers.insert(joker_card);
+                }
+                joker_card
+            })
+            .collect();
+
+        let round = Round {
+            cards_played: cards(file.cards_played),
+            cards_held_in_hand: cards(file.cards_held_in_hand),
+            jokers,
+        };
+        let in_hand: Vec<Card> = round
+            .cards_played
+            .iter()
+            .chain(&round.cards_held_in_hand)
+            .copied()
+            .collect();
+        let mut deck: Option<Vec<Card>> = file.deck.map(cards);
+        let mut remaining_deck: Option<Vec<Card>> = file.remaining_deck.map(cards);
+        match (&deck, &remaining_deck) {
+            (Some(full), None) => {
+                let mut remaining = Deck::from_cards(full.clone());
+                remaining.remove_all(&in_hand);
+                remaining_deck = Some(remaining.cards().to_vec());
+            }
+            (None, Some(remaining)) => {
+                deck = Some(remaining.iter().chain(&in_hand).copied().collect());
+            }
+            _ => {}
+        }
+
+        let input = RoundInput {
+            round,
+            card_extras,
+            custom_jokers,
+            consumables: file.consumables,
+            seed: file.seed,
+            deck_variant: file.deck_variant,
+            history: file.history,
+            deck,
+            remaining_deck,
+            joker_slots: file.joker_slots,
+            negative_jokers,
+            hand_levels: file.hand_levels,
+        };
+
+        let in_slots = input.jokers_in_slots();
+        if in_slots > input.joker_slots {
+            let negative = input.round.jokers.len() - in_slots;
+            return Err(format!(
+                "too many jokers: {} jokers need a slot but there are only {} joker slots{}",
+                in_slots,
+                input.joker_slots,
+                if negative > 0 {
+                    format!(" ({} Negative jokers take none)", negative)
+                } else {
+                    String::new()
+                }
+            ));
+        }
+        Ok(input)
+    }
+}
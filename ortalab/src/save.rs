@@ -0,0 +1,826 @@
+//! # Save Module
+//!
+//! This module turns a Balatro save file (`save.jkr`) into an ortalab round file,
+//! so the exact state of a real game can be scored.
+//!
+//! A save file is a DEFLATE-compressed Lua chunk, `return {...}`, holding one
+//! big table. The parts of it that ortalab reads are:
+//! - `cardAreas.hand.cards`: The cards in hand; the highlighted ones are the hand
+//!   about to be played, unless other cards are chosen when importing
+//! - `cardAreas.play.cards`: Cards already played, if the game was saved mid-hand
+//! - `cardAreas.jokers`: The jokers with their editions, and the joker slots
+//! - `GAME.hands`: The level of every poker hand, and how often each was played
+//! - `cardAreas.deck`, `cardAreas.discard`: The cards left to draw and the cards
+//!   already discarded, for the deck jokers
+//!
+//! The `history` section is written only for the jokers that read it (Supernova,
+//! Card Sharp, Obelisk, Ride the Bus) and the deck only for the deck jokers (Blue
+//! Joker, Erosion, Stone Joker, Steel Joker, Driver's License). Obelisk and Ride
+//! the Bus keep their streaks in their own `ability` table. A save without what
+//! such a joker needs reports the joker as unsupported.
+//!
+//! Cards and jokers are identified by their Balatro keys (`H_K` for the King of
+//! Hearts, `m_steel` for the Steel enhancement, `j_blueprint` for Blueprint).
+//! Anything ortalab does not model, such as most jokers or the Lucky enhancement,
+//! makes the import fail unless it is asked to leave them out.
+//!
+//! ## Key Components
+//! - `import_save()`: Reads a save file into an `ImportedRound`
+//! - `ImportedRound`: The round file written for a save, printed as YAML
+//! - `LuaValue`: A value of the Lua table structure saves are written in
+
+use enum_iterator::all;
+use ortalib::{Card, Edition, Enhancement, PokerHand, Rank, Suit};
+use serde::Serialize;
+use serde_yaml::Mapping;
+
+use crate::extras::{CardExtras, CardSpec, ExtraEnhancement};
+use crate::round::DEFAULT_JOKER_SLOTS;
+
+/// Largest decompressed save accepted, far above any real save
+const MAX_SAVE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Balatro's keys of the jokers ortalab implements, with their names in round files
+#[rustfmt::skip]
+const JOKER_KEYS: [(&str, &str); 43] = [
+    ("j_joker",            "Joker"),
+    ("j_jolly",            "Jolly Joker"),
+    ("j_zany",             "Zany Joker"),
+    ("j_mad",              "Mad Joker"),
+    ("j_crazy",            "Crazy Joker"),
+    ("j_droll",            "Droll Joker"),
+    ("j_sly",              "Sly Joker"),
+    ("j_wily",             "Wily Joker"),
+    ("j_clever",           "Clever Joker"),
+    ("j_devious",          "Devious Joker"),
+    ("j_crafty",           "Crafty Joker"),
+    ("j_abstract",         "Abstract Joker"),
+    ("j_raised_fist",      "Raised Fist"),
+    ("j_blackboard",       "Blackboard"),
+    ("j_baron",            "Baron"),
+    ("j_greedy_joker",     "Greedy Joker"),
+    ("j_lusty_joker",      "Lusty Joker"),
+    ("j_wrathful_joker",   "Wrathful Joker"),
+    ("j_gluttenous_joker", "Gluttonous Joker"),
+    ("j_fibonacci",        "Fibonacci"),
+    ("j_scary_face",       "Scary Face"),
+    ("j_even_steven",      "Even Steven"),
+    ("j_odd_todd",         "Odd Todd"),
+    ("j_photograph",       "Photograph"),
+    ("j_smiley",           "Smiley Face"),
+    ("j_flower_pot",       "Flower Pot"),
+    ("j_four_fingers",     "Four Fingers"),
+    ("j_shortcut",         "Shortcut"),
+    ("j_mime",             "Mime"),
+    ("j_pareidolia",       "Pareidolia"),
+    ("j_splash",           "Splash"),
+    ("j_sock_and_buskin",  "Sock And Buskin"),
+    ("j_smeared",          "Smeared Joker"),
+    ("j_blueprint",        "Blueprint"),
+    ("j_supernova",        "Supernova"),
+    ("j_card_sharp",       "Card Sharp"),
+    ("j_obelisk",          "Obelisk"),
+    ("j_ride_the_bus",     "Ride the Bus"),
+    ("j_blue_joker",       "Blue Joker"),
+    ("j_erosion",          "Erosion"),
+    ("j_stone",            "Stone Joker"),
+    ("j_steel_joker",      "Steel Joker"),
+    ("j_drivers_license",  "Driver's License"),
+];
+
+/// Jokers that read the `history` section
+const HISTORY_JOKERS: [&str; 4] = ["Supernova", "Card Sharp", "Obelisk", "Ride the Bus"];
+
+/// Jokers that read the deck
+const DECK_JOKERS: [&str; 5] = [
+    "Blue Joker",
+    "Erosion",
+    "Stone Joker",
+    "Steel Joker",
+    "Driver's License",
+];
+
+/// Mult Obelisk gains per hand of its streak in Balatro, which its `x_mult` is made of
+const OBELISK_STEP: f64 = 0.2;
+
+/// A value of a save's Lua table structure
+#[derive(Debug, Clone, PartialEq)]
+pub enum LuaValue {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Table(LuaTable),
+}
+
+/// A key of a Lua table: an array index or a field name
+#[derive(Debug, Clone, PartialEq)]
+pub enum LuaKey {
+    Index(f64),
+    Name(String),
+}
+
+/// A Lua table, with its entries in the order they were written
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LuaTable {
+    pub entries: Vec<(LuaKey, LuaValue)>,
+}
+
+impl LuaTable {
+    /// The value of a named field
+    pub fn get(&self, name: &str) -> Option<&LuaValue> {
+        self.entries.iter().find_map(|(key, value)| match key {
+            LuaKey::Name(key) if key == name => Some(value),
+            _ => None,
+        })
+    }
+
+    /// The values with array indices, in index order
+    pub fn array(&self) -> Vec<&LuaValue> {
+        let mut items: Vec<(f64, &LuaValue)> = self
+            .entries
+            .iter()
+            .filter_map(|(key, value)| match key {
+                LuaKey::Index(index) => Some((*index, value)),
+                LuaKey::Name(_) => None,
+            })
+            .collect();
+        items.sort_by(|a, b| a.0.total_cmp(&b.0));
+        items.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Follows a path of named fields, e.g. `["cardAreas", "hand", "cards"]`
+    pub fn path(&self, path: &[&str]) -> Option<&LuaValue> {
+        let (first, rest) = path.split_first()?;
+        let value = self.get(first)?;
+        if rest.is_empty() {
+            Some(value)
+        } else {
+            value.as_table()?.path(rest)
+        }
+    }
+
+    fn string(&self, name: &str) -> Option<&str> {
+        self.get(name)?.as_str()
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.get(name) == Some(&LuaValue::Bool(true))
+    }
+}
+
+impl LuaValue {
+    pub fn as_table(&self) -> Option<&LuaTable> {
+        match self {
+            LuaValue::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            LuaValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            LuaValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the Lua table constructors, strings, numbers and booleans saves are made of
+struct LuaParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl LuaParser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("invalid save data at byte {}: {}", self.pos, message)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(&format!("expected `{}`", byte as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// A run of identifier characters, e.g. `return`, `true` or a field name
+    fn word(&mut self) -> &str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default()
+    }
+
+    /// The whole chunk: `return` followed by one value
+    fn chunk(&mut self) -> Result<LuaValue, String> {
+        self.skip_whitespace();
+        if self.word() != "return" {
+            return Err(self.error("expected `return`"));
+        }
+        let value = self.value()?;
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(self.error("unexpected data after the saved table"));
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<LuaValue, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.table().map(LuaValue::Table),
+            Some(quote @ (b'"' | b'\'')) => self.string(quote).map(LuaValue::String),
+            Some(byte) if byte.is_ascii_alphabetic() => {
+                let start = self.pos;
+                match self.word() {
+                    "true" => Ok(LuaValue::Bool(true)),
+                    "false" => Ok(LuaValue::Bool(false)),
+                    "nil" => Ok(LuaValue::Nil),
+                    // `tostring` writes infinities and NaN as words
+                    _ => {
+                        self.pos = start;
+                        self.number().map(LuaValue::Number)
+                    }
+                }
+            }
+            Some(_) => self.number().map(LuaValue::Number),
+            None => Err(self.error("unexpected end of data")),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'+' | b'-'))
+        {
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default();
+        let number = match text.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16).map(|n| n as f64).ok(),
+            None => text.parse().ok(),
+        };
+        number.ok_or_else(|| {
+            self.pos = start;
+            self.error(&format!("invalid number `{}`", text))
+        })
+    }
+
+    fn string(&mut self, quote: u8) -> Result<String, String> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(byte) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            if byte == quote {
+                break;
+            }
+            if byte != b'\\' {
+                bytes.push(byte);
+                continue;
+            }
+            let Some(escape) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match escape {
+                b'n' | b'\n' => bytes.push(b'\n'),
+                b'r' => bytes.push(b'\r'),
+                b't' => bytes.push(b'\t'),
+                b'a' => bytes.push(0x07),
+                b'b' => bytes.push(0x08),
+                b'f' => bytes.push(0x0c),
+                b'v' => bytes.push(0x0b),
+                b'0'..=b'9' => {
+                    // Up to three decimal digits, e.g. `\0` or `\127`
+                    let start = self.pos - 1;
+                    while self.pos - start < 3 && self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                        self.pos += 1;
+                    }
+                    let digits =
+                        std::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default();
+                    let code: u8 = digits
+                        .parse()
+                        .map_err(|_| self.error(&format!("invalid escape `\\{}`", digits)))?;
+                    bytes.push(code);
+                }
+                other => bytes.push(other),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn table(&mut self) -> Result<LuaTable, String> {
+        self.expect(b'{')?;
+        let mut table = LuaTable::default();
+        let mut next_index = 1.0;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(table);
+                }
+                Some(b'[') => {
+                    self.pos += 1;
+                    let key = match self.value()? {
+                        LuaValue::String(name) => LuaKey::Name(name),
+                        LuaValue::Number(index) => LuaKey::Index(index),
+                        _ => return Err(self.error("table keys must be strings or numbers")),
+                    };
+                    self.expect(b']')?;
+                    self.expect(b'=')?;
+                    table.entries.push((key, self.value()?));
+                }
+                Some(byte) if byte.is_ascii_alphabetic() || byte == b'_' => {
+                    // Either `name = value` or a positional `true`, `false` or `nil`
+                    let start = self.pos;
+                    let name = self.word().to_string();
+                    self.skip_whitespace();
+                    if self.peek() == Some(b'=') {
+                        self.pos += 1;
+                        table.entries.push((LuaKey::Name(name), self.value()?));
+                    } else {
+                        self.pos = start;
+                        table
+                            .entries
+                            .push((LuaKey::Index(next_index), self.value()?));
+                        next_index += 1.0;
+                    }
+                }
+                Some(_) => {
+                    table
+                        .entries
+                        .push((LuaKey::Index(next_index), self.value()?));
+                    next_index += 1.0;
+                }
+                None => return Err(self.error("unterminated table")),
+            }
+            self.skip_whitespace();
+            if matches!(self.peek(), Some(b',' | b';')) {
+                self.pos += 1;
+            }
+        }
+    }
+}
+
+/// Decompresses a save file, accepting raw DEFLATE (as Balatro writes it), zlib
+/// or already decompressed data
+pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    if bytes.trim_ascii_start().starts_with(b"return") {
+        return Ok(bytes.to_vec());
+    }
+    let zlib_header = bytes.len() >= 2
+        && bytes[0] & 0x0f == 8
+        && (u16::from(bytes[0]) << 8 | u16::from(bytes[1])) % 31 == 0;
+    let inflated = if zlib_header {
+        miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(bytes, MAX_SAVE_SIZE)
+    } else {
+        miniz_oxide::inflate::decompress_to_vec_with_limit(bytes, MAX_SAVE_SIZE)
+    };
+    inflated.map_err(|err| format!("could not decompress the save: {}", err))
+}
+
+/// Parses a decompressed save into its Lua table
+pub fn parse_lua(data: &[u8]) -> Result<LuaTable, String> {
+    let mut parser = LuaParser {
+        input: data,
+        pos: 0,
+    };
+    match parser.chunk()? {
+        LuaValue::Table(table) => Ok(table),
+        _ => Err("invalid save data: the save is not a table".to_string()),
+    }
+}
+
+/// How a save is turned into a round
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Positions (from 1) of the cards in hand to play instead of the highlighted ones
+    pub play: Vec<usize>,
+    /// Leave out what ortalab does not support instead of failing
+    pub skip_unsupported: bool,
+}
+
+/// A round file made from a save
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedRound {
+    pub cards_played: Vec<String>,
+    pub cards_held_in_hand: Vec<String>,
+    pub jokers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joker_slots: Option<usize>,
+    /// Levels above 1, in poker hand order
+    #[serde(skip_serializing_if = "Mapping::is_empty")]
+    pub hand_levels: Mapping,
+    /// Earlier hands of the run, when a joker reads them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<ImportedHistory>,
+    /// Every card of the deck, when cards were discarded and a joker reads the deck
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deck: Option<Vec<String>>,
+    /// The cards left to draw, when a joker reads the deck
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_deck: Option<Vec<String>>,
+    /// What was left out because ortalab does not support it
+    #[serde(skip)]
+    pub skipped: Vec<String>,
+}
+
+/// The `history` section of a round file made from a save
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportedHistory {
+    /// Times each poker hand was played, in poker hand order
+    #[serde(skip_serializing_if = "Mapping::is_empty")]
+    pub hands_played: Mapping,
+    /// Poker hands played this round; saves only count them, so they are in poker hand order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hands_this_round: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consecutive_non_face_hands: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hands_without_most_played: Option<u32>,
+}
+
+impl ImportedRound {
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|err| err.to_string())
+    }
+}
+
+/// Collects what ortalab does not support while a save is read
+struct Unsupported {
+    skip: bool,
+    found: Vec<String>,
+}
+
+impl Unsupported {
+    /// Notes something unsupported; it is left out when skipping, and fails the import otherwise
+    fn note(&mut self, what: String) {
+        self.found.push(what);
+    }
+
+    fn finish(self) -> Result<Vec<String>, String> {
+        if self.skip || self.found.is_empty() {
+            return Ok(self.found);
+        }
+        Err(format!(
+            "the save uses what ortalab does not support: {} (import with --skip-unsupported to leave them out)",
+            self.found.join(", ")
+        ))
+    }
+}
+
+/// The edition of a card or joker; Negative is `None` with the second value set
+fn edition(card: &LuaTable, unsupported: &mut Unsupported) -> (Option<Edition>, bool) {
+    let Some(edition) = card.get("edition").and_then(LuaValue::as_table) else {
+        return (None, false);
+    };
+    // Newer saves name the edition in `type`, older ones only set its flag
+    let kind = edition.string("type").or_else(|| {
+        ["foil", "holo", "polychrome", "negative"]
+            .into_iter()
+            .find(|&kind| edition.flag(kind))
+    });
+    match kind {
+        Some("foil") => (Some(Edition::Foil), false),
+        Some("holo") => (Some(Edition::Holographic), false),
+        Some("polychrome") => (Some(Edition::Polychrome), false),
+        Some("negative") => (None, true),
+        Some(other) => {
+            unsupported.note(format!("the `{}` edition", other));
+            (None, false)
+        }
+        None => (None, false),
+    }
+}
+
+/// A playing card from its save table, written as a card string
+fn card_spec(card: &LuaTable, unsupported: &mut Unsupported) -> Result<String, String> {
+    let key = card
+        .path(&["save_fields", "card"])
+        .and_then(LuaValue::as_str)
+        .ok_or("a playing card has no `save_fields.card` key")?;
+    let invalid = || format!("invalid card key `{}`", key);
+    let (suit, rank) = key.split_once('_').ok_or_else(invalid)?;
+    let suit = match suit {
+        "S" => Suit::Spades,
+        "H" => Suit::Hearts,
+        "C" => Suit::Clubs,
+        "D" => Suit::Diamonds,
+        _ => return Err(invalid()),
+    };
+    let rank = match rank {
+        "2" => Rank::Two,
+        "3" => Rank::Three,
+        "4" => Rank::Four,
+        "5" => Rank::Five,
+        "6" => Rank::Six,
+        "7" => Rank::Seven,
+        "8" => Rank::Eight,
+        "9" => Rank::Nine,
+        "T" => Rank::Ten,
+        "J" => Rank::Jack,
+        "Q" => Rank::Queen,
+        "K" => Rank::King,
+        "A" => Rank::Ace,
+        _ => return Err(invalid()),
+    };
+
+    let mut extras = CardExtras::default();
+    let enhancement = match card
+        .path(&["save_fields", "center"])
+        .and_then(LuaValue::as_str)
+    {
+        None | Some("c_base") => None,
+        Some("m_bonus") => Some(Enhancement::Bonus),
+        Some("m_mult") => Some(Enhancement::Mult),
+        Some("m_wild") => Some(Enhancement::Wild),
+        Some("m_glass") => Some(Enhancement::Glass),
+        Some("m_steel") => Some(Enhancement::Steel),
+        Some("m_gold") => {
+            extras.enhancement = Some(ExtraEnhancement::Gold);
+            None
+        }
+        Some("m_stone") => {
+            extras.enhancement = Some(ExtraEnhancement::Stone);
+            None
+        }
+        Some(other) => {
+            unsupported.note(format!("the `{}` enhancement on {}{}", other, rank, suit));
+            None
+        }
+    };
+    let (edition, negative) = edition(card, unsupported);
+    extras.negative = negative;
+    if let Some(seal) = card.string("seal") {
+        match seal.parse() {
+            Ok(seal) => extras.seal = Some(seal),
+            Err(_) => unsupported.note(format!("the `{}` seal on {}{}", seal, rank, suit)),
+        }
+    }
+    extras.debuffed = card.flag("debuff");
+    extras.face_down = card.string("facing") == Some("back");
+
+    let spec = CardSpec {
+        card: Card::new(rank, suit, enhancement, edition),
+        extras,
+    };
+    Ok(spec.to_string())
+}
+
+/// A joker from its save table: its name and its joker string
+fn joker_spec(
+    joker: &LuaTable,
+    unsupported: &mut Unsupported,
+) -> Result<Option<(&'static str, String)>, String> {
+    let key = joker
+        .path(&["save_fields", "center"])
+        .and_then(LuaValue::as_str)
+        .ok_or("a joker has no `save_fields.center` key")?;
+    let Some(&(_, name)) = JOKER_KEYS.iter().find(|&&(joker_key, _)| joker_key == key) else {
+        let label = joker.string("label").unwrap_or(key);
+        unsupported.note(format!("the joker {} (`{}`)", label, key));
+        return Ok(None);
+    };
+    let spec = match edition(joker, unsupported) {
+        (Some(edition), _) => format!("{} {}", name, edition),
+        (None, true) => format!("{} Negative", name),
+        (None, false) => name.to_string(),
+    };
+    Ok(Some((name, spec)))
+}
+
+/// A number from a joker's `ability` table, e.g. the Mult Ride the Bus has gained
+fn ability(joker: &LuaTable, field: &str) -> Option<f64> {
+    joker.path(&["ability", field])?.as_number()
+}
+
+/// A field of every poker hand in `GAME.hands`, e.g. `level` or `played`
+fn hand_counts(save: &LuaTable, field: &str) -> Vec<(PokerHand, u32)> {
+    let Some(hands) = save.path(&["GAME", "hands"]).and_then(LuaValue::as_table) else {
+        return Vec::new();
+    };
+    all::<PokerHand>()
+        .filter_map(|hand| {
+            // Balatro writes `Three of a Kind` where ortalib writes `Three Of A Kind`
+            let name = hand.to_string();
+            let count = hands
+                .entries
+                .iter()
+                .find(
+                    |(key, _)| matches!(key, LuaKey::Name(key) if key.eq_ignore_ascii_case(&name)),
+                )
+                .and_then(|(_, value)| value.as_table()?.get(field)?.as_number())?;
+            Some((hand, count as u32))
+        })
+        .collect()
+}
+
+/// The card strings of every card in some card areas
+fn area_specs(
+    save: &LuaTable,
+    areas: &[&str],
+    unsupported: &mut Unsupported,
+) -> Result<Vec<String>, String> {
+    let mut specs = Vec::new();
+    for area in areas {
+        for card in area_cards(save, area)? {
+            specs.push(card_spec(card, unsupported)?);
+        }
+    }
+    Ok(specs)
+}
+
+/// The tables of the cards in one card area, e.g. `hand` or `jokers`
+fn area_cards<'a>(save: &'a LuaTable, area: &str) -> Result<Vec<&'a LuaTable>, String> {
+    let Some(cards) = save.path(&["cardAreas", area, "cards"]) else {
+        return Ok(Vec::new());
+    };
+    cards
+        .as_table()
+        .ok_or_else(|| format!("`cardAreas.{}.cards` is not a table", area))?
+        .array()
+        .into_iter()
+        .map(|card| {
+            card.as_table()
+                .ok_or_else(|| format!("a card of `cardAreas.{}` is not a table", area))
+        })
+        .collect()
+}
+
+/// Reads a save file into a round file
+pub fn import_save(bytes: &[u8], options: &ImportOptions) -> Result<ImportedRound, String> {
+    let save = parse_lua(&decompress(bytes)?)?;
+    let mut unsupported = Unsupported {
+        skip: options.skip_unsupported,
+        found: Vec::new(),
+    };
+
+    // Played cards: those already in play, then the chosen or highlighted cards in hand
+    let hand = area_cards(&save, "hand")?;
+    if let Some(&position) = options
+        .play
+        .iter()
+        .find(|&&position| position == 0 || position > hand.len())
+    {
+        return Err(format!(
+            "cannot play card {}: the hand has {} cards (positions start at 1)",
+            position,
+            hand.len()
+        ));
+    }
+    let is_played = |position: usize, card: &LuaTable| {
+        if options.play.is_empty() {
+            card.flag("highlighted")
+        } else {
+            options.play.contains(&(position + 1))
+        }
+    };
+    let mut cards_played = Vec::new();
+    for card in area_cards(&save, "play")? {
+        cards_played.push(card_spec(card, &mut unsupported)?);
+    }
+    let mut cards_held_in_hand = Vec::new();
+    for (position, &card) in hand.iter().enumerate() {
+        let spec = card_spec(card, &mut unsupported)?;
+        if is_played(position, card) {
+            cards_played.push(spec);
+        } else {
+            cards_held_in_hand.push(spec);
+        }
+    }
+    if cards_played.is_empty() {
+        return Err(format!(
+            "the save has no played or highlighted cards; choose cards to play from the {} in hand, e.g. --play 1,2",
+            hand.len()
+        ));
+    }
+
+    let played_counts = hand_counts(&save, "played");
+    let round_counts = hand_counts(&save, "played_this_round");
+    let has_deck = save.path(&["cardAreas", "deck", "cards"]).is_some();
+    let mut jokers = Vec::new();
+    let mut history = ImportedHistory::default();
+    let (mut needs_history, mut needs_deck) = (false, false);
+    for joker in area_cards(&save, "jokers")? {
+        let Some((name, spec)) = joker_spec(joker, &mut unsupported)? else {
+            continue;
+        };
+        // Balatro keeps the streaks of Obelisk and Ride the Bus in the joker itself
+        let missing = match name {
+            "Supernova" if played_counts.is_empty() => Some("hand counts"),
+            "Card Sharp" if round_counts.is_empty() => Some("hand counts"),
+            "Obelisk" => match ability(joker, "x_mult") {
+                Some(x_mult) => {
+                    let streak = ((x_mult - 1.0) / OBELISK_STEP).round().max(0.0) as u32;
+                    history.hands_without_most_played = Some(streak);
+                    None
+                }
+                None => Some("streak"),
+            },
+            "Ride the Bus" => match ability(joker, "mult") {
+                Some(mult) => {
+                    history.consecutive_non_face_hands = Some(mult.max(0.0) as u32);
+                    None
+                }
+                None => Some("streak"),
+            },
+            _ if DECK_JOKERS.contains(&name) && !has_deck => Some("deck"),
+            _ => None,
+        };
+        if let Some(missing) = missing {
+            unsupported.note(format!("{} (the save has no {} for it)", name, missing));
+            continue;
+        }
+        needs_history |= HISTORY_JOKERS.contains(&name);
+        needs_deck |= DECK_JOKERS.contains(&name);
+        jokers.push(spec);
+    }
+    // Balatro counts Negative jokers in its joker limit, ortalab does not give them a slot
+    let negative_jokers = jokers
+        .iter()
+        .filter(|joker| joker.ends_with(" Negative"))
+        .count();
+    let joker_slots = save
+        .path(&["cardAreas", "jokers", "config", "card_limit"])
+        .and_then(LuaValue::as_number)
+        .map(|limit| (limit as usize).saturating_sub(negative_jokers))
+        .filter(|&slots| slots != DEFAULT_JOKER_SLOTS);
+
+    let mut hand_levels = Mapping::new();
+    for (hand, level) in hand_counts(&save, "level") {
+        if level > 1 {
+            hand_levels.insert(hand.to_string().into(), level.into());
+        }
+    }
+
+    // The counts in a save are taken as the hands before the one being scored
+    for &(hand, played) in &played_counts {
+        if played > 0 {
+            history
+                .hands_played
+                .insert(hand.to_string().into(), played.into());
+        }
+    }
+    for &(hand, played) in &round_counts {
+        history
+            .hands_this_round
+            .extend((0..played).map(|_| hand.to_string()));
+    }
+
+    let (deck, remaining_deck) = if needs_deck {
+        let remaining = area_specs(&save, &["deck"], &mut unsupported)?;
+        // Without discards the round file rebuilds the full deck from the other cards
+        let deck = if area_cards(&save, "discard")?.is_empty() {
+            None
+        } else {
+            Some(area_specs(
+                &save,
+                &["deck", "discard", "play", "hand"],
+                &mut unsupported,
+            )?)
+        };
+        (deck, Some(remaining))
+    } else {
+        (None, None)
+    };
+
+    Ok(ImportedRound {
+        cards_played,
+        cards_held_in_hand,
+        jokers,
+        joker_slots,
+        hand_levels,
+        history: needs_history.then_some(history),
+        deck,
+        remaining_deck,
+        skipped: unsupported.finish()?,
+    })
+}
@@ -0,0 +1,192 @@
+//! # Variants Module
+//!
+//! This module describes the deck and stake a run is played with, and the
+//! blind targets they lead to.
+//!
+//! ## Key Components
+//! - `DeckVariant`: The deck chosen at the start of a run (Plasma, Checkered, Erratic, ...)
+//! - `Stake`: The difficulty of a run; each stake adds to the rules of the ones below it
+//! - `blind_target()`: The score needed to clear a blind
+//!
+//! ## Deck Variants
+//! - Plasma Deck: Chips and Mult are balanced at the end of scoring, and blind
+//!   targets are doubled
+//! - Checkered Deck: 26 Spades and 26 Hearts instead of one card of every suit
+//! - Erratic Deck: Every card has a random rank and suit
+//!
+//! ## Stakes
+//! - Red Stake: The Small Blind gives no reward money
+//! - Green Stake: Blind targets scale faster
+//! - Purple Stake: Blind targets scale faster still
+//!
+//! The other stakes only add rules that are not simulated (discards and joker
+//! stickers), so they play like the stake below them.
+
+use std::fmt;
+
+use enum_iterator::all;
+use itertools::Itertools;
+use ortalib::{Card, Chips, Mult, Rank, Suit};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::economy::Blind;
+
+/// The deck a run is played with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeckVariant {
+    /// Any deck without an effect on scoring or composition (e.g. the Red Deck)
+    #[default]
+    Standard,
+    /// Balances Chips and Mult at the end of scoring; blind targets are doubled
+    Plasma,
+    /// 26 Spades and 26 Hearts
+    Checkered,
+    /// Every card has a random rank and suit
+    Erratic,
+}
+
+impl fmt::Display for DeckVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DeckVariant::Standard => "Standard Deck",
+            DeckVariant::Plasma => "Plasma Deck",
+            DeckVariant::Checkered => "Checkered Deck",
+            DeckVariant::Erratic => "Erratic Deck",
+        };
+        f.pad(name)
+    }
+}
+
+impl DeckVariant {
+    /// The 52 cards the deck starts with; `seed` is only used by the Erratic Deck
+    pub fn cards(&self, seed: u64) -> Vec<Card> {
+        let standard = all::<Suit>().cartesian_product(all::<Rank>());
+        match self {
+            DeckVariant::Standard | DeckVariant::Plasma => standard
+                .map(|(suit, rank)| Card::new(rank, suit, None, None))
+                .collect(),
+            DeckVariant::Checkered => standard
+                .map(|(suit, rank)| {
+                    let suit = match suit {
+                        Suit::Clubs => Suit::Spades,
+                        Suit::Diamonds => Suit::Hearts,
+                        suit => suit,
+                    };
+                    Card::new(rank, suit, None, None)
+                })
+                .collect(),
+            DeckVariant::Erratic => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let ranks: Vec<Rank> = all::<Rank>().collect();
+                let suits: Vec<Suit> = all::<Suit>().collect();
+                standard
+                    .map(|_| {
+                        let rank = *ranks.choose(&mut rng).expect("there are ranks");
+                        let suit = *suits.choose(&mut rng).expect("there are suits");
+                        Card::new(rank, suit, None, None)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Applies the deck's effect to the final Chips and Mult, returning whether it had one
+    pub fn balance(&self, chips: &mut Chips, mult: &mut Mult) -> bool {
+        if *self != DeckVariant::Plasma {
+            return false;
+        }
+        let balanced = ((*chips + *mult) / 2.0).floor();
+        *chips = balanced;
+        *mult = balanced;
+        true
+    }
+
+    /// Multiplier applied to every blind target
+    pub fn blind_scaling(&self) -> f64 {
+        match self {
+            DeckVariant::Plasma => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// The difficulty of a run, from easiest to hardest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Stake {
+    #[default]
+    White,
+    Red,
+    Green,
+    Black,
+    Blue,
+    Purple,
+    Orange,
+    Gold,
+}
+
+impl fmt::Display for Stake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{:?} Stake", self))
+    }
+}
+
+/// Base blind targets for antes 1 to 8, by stake scaling
+#[rustfmt::skip]
+const ANTE_TARGETS: [[f64; 8]; 3] = [
+    [300.0, 800.0, 2_000.0, 5_000.0, 11_000.0, 20_000.0, 35_000.0, 50_000.0],
+    [300.0, 900.0, 2_600.0, 8_000.0, 20_000.0, 36_000.0, 60_000.0, 100_000.0],
+    [300.0, 1_000.0, 3_200.0, 9_000.0, 25_000.0, 60_000.0, 110_000.0, 200_000.0],
+];
+
+/// Base target of the antes before the first one
+const ANTE_ZERO_TARGET: f64 = 100.0;
+
+impl Stake {
+    /// Money earned for clearing `blind`
+    pub fn blind_reward(&self, blind: Blind) -> u32 {
+        if blind == Blind::Small && *self >= Stake::Red {
+            0
+        } else {
+            blind.reward()
+        }
+    }
+
+    /// Which row of `ANTE_TARGETS` the stake uses
+    fn scaling(&self) -> usize {
+        if *self >= Stake::Purple {
+            2
+        } else if *self >= Stake::Green {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Base target of an ante, before the blind and deck multipliers
+    pub fn ante_target(&self, ante: usize) -> f64 {
+        let targets = &ANTE_TARGETS[self.scaling()];
+        match ante {
+            0 => ANTE_ZERO_TARGET,
+            1..=8 => targets[ante - 1],
+            _ => {
+                // Past ante 8 targets grow exponentially, keeping two significant digits
+                let extra = (ante - 8) as f64;
+                let target =
+                    targets[7] * (1.6 + (0.75 * extra).powf(1.0 + 0.2 * extra)).powf(extra);
+                let step = 10f64.powf((target.log10() - 1.0).floor());
+                target - target % step
+            }
+        }
+    }
+}
+
+/// Score needed to clear `blind` in `ante`
+pub fn blind_target(ante: usize, blind: Blind, stake: Stake, deck: DeckVariant) -> f64 {
+    stake.ante_target(ante) * blind.scaling() * deck.blind_scaling()
+}
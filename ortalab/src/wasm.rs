@@ -0,0 +1,13 @@
+//! # WebAssembly Module
+//!
+//! This module exports the engine to JavaScript when built for wasm32.
+
+use wasm_bindgen::prelude::*;
+
+/// Scores a round written as JSON (see `ortalab schema`) and returns the JSON
+/// of its `ScoreReport`: the score, or the game error, and the explain trace
+#[wasm_bindgen]
+pub fn score(round_json: &str) -> Result<String, JsError> {
+    let report = crate::score_json(round_json).map_err(|err| JsError::new(&err))?;
+    serde_json::to_string(&report).map_err(|err| JsError::new(&err.to_string()))
+}